@@ -3,6 +3,51 @@ import { ApiPromise, WsProvider } from '@polkadot/api';
 import { Keyring } from '@polkadot/keyring';
 import { ArrowRight, User, GraduationCap, Building2, Loader2, AlertCircle, CheckCircle, Edit2, X, Save } from 'lucide-react';
 
+// Student record as shown in the UI; committed or erased records have no personal data
+const decodeStudent = (id, student) => {
+  const personal = student.personal.isSome ? student.personal.unwrap() : null;
+  const dob = personal && personal.dateOfBirth;
+  const dateOfBirth = dob
+    ? `${dob.year.toString().padStart(4, '0')}-${dob.month.toString().padStart(2, '0')}-${dob.day.toString().padStart(2, '0')}`
+    : '';
+  let age = '';
+  if (dob) {
+    const today = new Date();
+    age = today.getFullYear() - dob.year.toNumber();
+    if (today.getMonth() + 1 < dob.month.toNumber() ||
+        (today.getMonth() + 1 === dob.month.toNumber() && today.getDate() < dob.day.toNumber())) {
+      age -= 1;
+    }
+  }
+  return {
+    id,
+    name: personal ? new TextDecoder().decode(personal.givenName) : '',
+    surname: personal ? new TextDecoder().decode(personal.familyName) : '',
+    dateOfBirth,
+    age,
+    gender: personal ? personal.gender.toString() : 'Undisclosed',
+    status: student.status.toString(),
+    hasGraduated: ['Graduated', 'Transferred', 'Employed'].includes(student.status.toString())
+  };
+};
+
+// Institution registered in the genesis presets, administered by Alice
+const UNIVERSITY_INSTITUTION_ID = 0;
+
+// `PersonalDataInput` argument of create_student and update_student
+const personalDataInput = ({ name, surname, dateOfBirth, gender }) => {
+  const [year, month, day] = dateOfBirth.split('-').map((part) => parseInt(part));
+  return {
+    givenName: Array.from(new TextEncoder().encode(name)),
+    middleName: [],
+    familyName: Array.from(new TextEncoder().encode(surname)),
+    dateOfBirth: { year, month, day },
+    gender,
+    nationalIdHash: null,
+    emailHash: null
+  };
+};
+
 const XCMStudentVisualizer = () => {
   const [apis, setApis] = useState({ university: null, company: null });
   const [loading, setLoading] = useState(true);
@@ -19,7 +64,7 @@ const XCMStudentVisualizer = () => {
   const [formData, setFormData] = useState({
     name: '',
     surname: '',
-    age: '',
+    dateOfBirth: '',
     gender: 'Male'
   });
   const [creating, setCreating] = useState(false);
@@ -31,7 +76,7 @@ const XCMStudentVisualizer = () => {
   const [editFormData, setEditFormData] = useState({
     name: '',
     surname: '',
-    age: '',
+    dateOfBirth: '',
     gender: 'Male'
   });
   const [updating, setUpdating] = useState(false);
@@ -121,14 +166,7 @@ const XCMStudentVisualizer = () => {
         const studentOption = await uniApi.query[pallet].students(i);
         if (studentOption.isSome) {
           const student = studentOption.unwrap();
-          uniStudentsList.push({
-            id: i,
-            name: new TextDecoder().decode(student.name),
-            surname: new TextDecoder().decode(student.surname),
-            age: student.age.toNumber(),
-            gender: student.gender.toString(),
-            hasGraduated: student.hasGraduated.valueOf()
-          });
+          uniStudentsList.push(decodeStudent(i, student));
         }
       }
       setUniversityStudents(uniStudentsList);
@@ -142,14 +180,7 @@ const XCMStudentVisualizer = () => {
         const studentOption = await compApi.query[pallet].students(i);
         if (studentOption.isSome) {
           const student = studentOption.unwrap();
-          compStudentsList.push({
-            id: i,
-            name: new TextDecoder().decode(student.name),
-            surname: new TextDecoder().decode(student.surname),
-            age: student.age.toNumber(),
-            gender: student.gender.toString(),
-            hasGraduated: student.hasGraduated.valueOf()
-          });
+          compStudentsList.push(decodeStudent(i, student));
         }
       }
       setCompanyStudents(compStudentsList);
@@ -175,7 +206,7 @@ const XCMStudentVisualizer = () => {
     setEditFormData({
       name: student.name,
       surname: student.surname,
-      age: student.age.toString(),
+      dateOfBirth: student.dateOfBirth,
       gender: student.gender
     });
   };
@@ -185,13 +216,13 @@ const XCMStudentVisualizer = () => {
     setEditFormData({
       name: '',
       surname: '',
-      age: '',
+      dateOfBirth: '',
       gender: 'Male'
     });
   };
 
   const updateStudent = async (studentId) => {
-    if (!editFormData.name || !editFormData.surname || !editFormData.age) {
+    if (!editFormData.name || !editFormData.surname || !editFormData.dateOfBirth) {
       alert('Please fill in all fields');
       return;
     }
@@ -204,15 +235,12 @@ const XCMStudentVisualizer = () => {
     setUpdating(true);
 
     try {
-      const nameBytes = Array.from(new TextEncoder().encode(editFormData.name));
-      const surnameBytes = Array.from(new TextEncoder().encode(editFormData.surname));
-      const age = parseInt(editFormData.age);
-      const gender = editFormData.gender;
+      const data = personalDataInput(editFormData);
 
-      console.log('Updating student:', { studentId, nameBytes, surnameBytes, age, gender });
+      console.log('Updating student:', { studentId, data });
 
       const unsub = await apis.university.tx[palletName]
-        .updateStudent(studentId, nameBytes, surnameBytes, age, gender)
+        .updateStudent(studentId, data)
         .signAndSend(alice, ({ status, events }) => {
           if (status.isInBlock) {
             console.log(`Update included in block ${status.asInBlock}`);
@@ -240,7 +268,7 @@ const XCMStudentVisualizer = () => {
   };
 
   const createStudent = async () => {
-    if (!formData.name || !formData.surname || !formData.age) {
+    if (!formData.name || !formData.surname || !formData.dateOfBirth) {
       alert('Please fill in all fields');
       return;
     }
@@ -253,15 +281,12 @@ const XCMStudentVisualizer = () => {
     setCreating(true);
 
     try {
-      const nameBytes = Array.from(new TextEncoder().encode(formData.name));
-      const surnameBytes = Array.from(new TextEncoder().encode(formData.surname));
-      const age = parseInt(formData.age);
-      const gender = formData.gender;
+      const data = personalDataInput(formData);
 
-      console.log('Creating student:', { nameBytes, surnameBytes, age, gender });
+      console.log('Creating student:', data);
 
       const unsub = await apis.university.tx[palletName]
-        .createStudent(nameBytes, surnameBytes, age, gender)
+        .createStudent(UNIVERSITY_INSTITUTION_ID, data)
         .signAndSend(alice, ({ status, events }) => {
           if (status.isInBlock) {
             console.log(`Transaction included in block ${status.asInBlock}`);
@@ -275,7 +300,7 @@ const XCMStudentVisualizer = () => {
             });
 
             loadStudentsFromChains(apis.university, apis.company, palletName);
-            setFormData({ name: '', surname: '', age: '', gender: 'Male' });
+            setFormData({ name: '', surname: '', dateOfBirth: '', gender: 'Male' });
             setCreating(false);
             unsub();
           }
@@ -300,7 +325,7 @@ const XCMStudentVisualizer = () => {
       console.log(`Graduating student ID ${studentId}...`);
 
       const unsub = await apis.university.tx[palletName]
-        .graduateStudent(studentId)
+        .graduateStudent(studentId, null)
         .signAndSend(alice, async ({ status, events }) => {
           if (status.isInBlock) {
             console.log(`Graduation transaction in block ${status.asInBlock}`);
@@ -497,13 +522,11 @@ const XCMStudentVisualizer = () => {
               className="px-4 py-2 border border-gray-300 rounded-lg focus:ring-2 focus:ring-indigo-500 focus:border-transparent"
             />
             <input
-              type="number"
-              name="age"
-              value={formData.age}
+              type="date"
+              name="dateOfBirth"
+              value={formData.dateOfBirth}
               onChange={handleInputChange}
-              placeholder="Age"
-              min="18"
-              max="100"
+              placeholder="Date of birth"
               className="px-4 py-2 border border-gray-300 rounded-lg focus:ring-2 focus:ring-indigo-500 focus:border-transparent"
             />
             <select
@@ -587,13 +610,11 @@ const XCMStudentVisualizer = () => {
                           className="w-full px-3 py-2 border border-gray-300 rounded-lg focus:ring-2 focus:ring-blue-500"
                         />
                         <input
-                          type="number"
-                          name="age"
-                          value={editFormData.age}
+                          type="date"
+                          name="dateOfBirth"
+                          value={editFormData.dateOfBirth}
                           onChange={handleEditInputChange}
-                          placeholder="Age"
-                          min="18"
-                          max="100"
+                          placeholder="Date of birth"
                           className="w-full px-3 py-2 border border-gray-300 rounded-lg focus:ring-2 focus:ring-blue-500"
                         />
                         <select
@@ -67,3 +67,24 @@ pub fn local_chain_spec() -> ChainSpec {
 	.with_properties(properties)
 	.build()
 }
+
+pub fn council_chain_spec() -> ChainSpec {
+	// Give your base currency a unit name and decimal places
+	let mut properties = sc_chain_spec::Properties::new();
+	properties.insert("tokenSymbol".into(), "UNIT".into());
+	properties.insert("tokenDecimals".into(), 12.into());
+	properties.insert("ss58Format".into(), 42.into());
+
+	#[allow(deprecated)]
+	ChainSpec::builder(
+		runtime::WASM_BINARY.expect("WASM binary was not built, please build it!"),
+		Extensions { relay_chain: RELAY_CHAIN.into(), para_id: runtime::PARACHAIN_ID },
+	)
+	.with_name("Council Testnet")
+	.with_id("council_testnet")
+	.with_chain_type(ChainType::Local)
+	.with_genesis_config_preset_name(runtime::COUNCIL_RUNTIME_PRESET)
+	.with_protocol_id("template-council")
+	.with_properties(properties)
+	.build()
+}
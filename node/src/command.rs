@@ -22,6 +22,7 @@ fn load_spec(id: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
 		"dev" => Box::new(chain_spec::development_chain_spec()),
 		"template-rococo" => Box::new(chain_spec::local_chain_spec()),
 		"" | "local" => Box::new(chain_spec::local_chain_spec()),
+		"council" => Box::new(chain_spec::council_chain_spec()),
 		path => Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
 	})
 }
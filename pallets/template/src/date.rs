@@ -0,0 +1,84 @@
+//! Calendar dates in the proleptic Gregorian calendar, used for dates of birth.
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// A calendar date. Only construct it from untrusted input through [`Date::is_valid`].
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+	TypeInfo,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Debug,
+	Default,
+)]
+pub struct Date {
+	pub year: u16,
+	/// 1 to 12.
+	pub month: u8,
+	/// 1 to the number of days in the month.
+	pub day: u8,
+}
+
+impl Date {
+	/// Whether the date exists in the calendar.
+	pub fn is_valid(&self) -> bool {
+		(1..=12).contains(&self.month) &&
+			self.day >= 1 &&
+			self.day <= days_in_month(self.year, self.month)
+	}
+
+	/// The date `secs` seconds after the unix epoch, in UTC.
+	pub fn from_unix_secs(secs: u64) -> Self {
+		civil_from_days((secs / SECONDS_PER_DAY) as i64)
+	}
+
+	/// Full years elapsed between `self` and `today`, or `None` if `today` is earlier.
+	pub fn years_until(&self, today: &Date) -> Option<u32> {
+		if today < self {
+			return None
+		}
+		let mut years = u32::from(today.year - self.year);
+		if (today.month, today.day) < (self.month, self.day) {
+			years -= 1;
+		}
+		Some(years)
+	}
+}
+
+fn is_leap_year(year: u16) -> bool {
+	(year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+	match month {
+		2 if is_leap_year(year) => 29,
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31,
+	}
+}
+
+/// Date of the day `days` after 1970-01-01, see
+/// <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> Date {
+	let z = days + 719_468;
+	let era = z.div_euclid(146_097);
+	let doe = z.rem_euclid(146_097);
+	let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let day = doy - (153 * mp + 2) / 5 + 1;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 };
+	let year = yoe + era * 400 + i64::from(month <= 2);
+	Date { year: year as u16, month: month as u8, day: day as u8 }
+}
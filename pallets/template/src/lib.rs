@@ -1,5 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 pub use pallet::*;
 
 #[cfg(test)]
@@ -8,6 +10,9 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod date;
+pub mod migrations;
+pub mod runtime_api;
 pub mod weights;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -16,12 +21,22 @@ mod benchmarking;
 
 #[frame::pallet(dev_mode)]
 pub mod pallet {
-	use frame::prelude::*;
-	use scale_info::prelude::vec::Vec;
-	use polkadot_sdk::staging_xcm::latest::{prelude::*, SendXcm,};
-	use scale_info::prelude::vec;
+	pub use crate::date::Date;
 	use codec::Encode;
-
+	use frame::{
+		deps::frame_support::storage::with_storage_layer,
+		prelude::*,
+		traits::{
+			fungible::{self, Mutate as _},
+			tokens::Preservation,
+			AccountIdConversion, Hash, TryConvert, UniqueSaturatedInto, UnixTime,
+		},
+	};
+	use polkadot_sdk::{
+		staging_xcm::latest::{prelude::*, SendXcm},
+		staging_xcm_executor::traits::ConvertLocation,
+	};
+	use scale_info::prelude::{vec, vec::Vec};
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -30,7 +45,7 @@ pub mod pallet {
 		/// A type representing the weights required by the dispatchables of this pallet.
 		type WeightInfo: crate::weights::WeightInfo;
 
-		/// Maximum bytes allowed for a student's name
+		/// Maximum bytes allowed for a student's given and middle name
 		#[pallet::constant]
     	type MaxNameLen: Get<u32>;
 
@@ -38,16 +53,188 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxSurnameLen: Get<u32>;
 
+		/// Maximum age, in years, a date of birth can correspond to
+		#[pallet::constant]
+		type MaxAge: Get<u32>;
+
+		/// Current time, used to derive ages from dates of birth
+		type UnixTime: UnixTime;
+
+		/// Maximum bytes allowed for the off-chain reference of committed personal data
+		#[pallet::constant]
+		type MaxReferenceLen: Get<u32>;
+
+		/// Maximum number of changes kept in a student's history
+		#[pallet::constant]
+		type MaxHistoryLen: Get<u32>;
+
 		type XcmSender: SendXcm;
 
+		/// Origin of calls sent by this pallet on a sibling chain, resolving to the sender's
+		/// location (the sibling parachain, possibly followed by the signing account). Accounts
+		/// of a sibling can send calls too, so only [`Pallet::receive_student`] accepts them.
+		type XcmOrigin: EnsureOrigin<
+			<Self as frame_system::Config>::RuntimeOrigin,
+			Success = Location,
+		>;
+
+		/// Converts an owner account into the interior location the outgoing XCM descends into,
+		/// so the destination can derive the owner from the XCM origin.
+		type AccountIdToLocation: for<'a> TryConvert<&'a Self::AccountId, Location>;
+
+		/// Converts a beneficiary location into an account. Both chains are expected to use the
+		/// same conversion, so the source can reject beneficiaries the destination cannot map.
+		type LocationToAccountId: ConvertLocation<Self::AccountId>;
+
 		type RuntimeCall: From<Call<Self>> + Encode;
 
 		#[pallet::constant]
 		type GraduationDestinationPara: Get<u32>;
+
+		/// Institution receiving graduates on [`Config::GraduationDestinationPara`]
+		#[pallet::constant]
+		type GraduationDestinationInstitution: Get<u32>;
+
+		/// Number of blocks a student has to approve a requested transfer
+		#[pallet::constant]
+		type TransferApprovalPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Number of blocks a deleted student can be restored before it is purged
+		#[pallet::constant]
+		type DeletionGracePeriod: Get<BlockNumberFor<Self>>;
+
+		/// Maximum number of students deleted in the same block
+		#[pallet::constant]
+		type MaxDeletionsPerBlock: Get<u32>;
+
+		/// Maximum number of students a single account can own
+		#[pallet::constant]
+		type MaxStudentsPerOwner: Get<u32>;
+
+		/// Origin administering the registry, e.g. root or a council majority. It registers
+		/// institutions, changes their accreditation and can act in place of owners and admins.
+		type InstitutionRegistrar: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+		/// Maximum bytes allowed for an institution's name
+		#[pallet::constant]
+		type MaxInstitutionNameLen: Get<u32>;
+
+		/// Maximum number of admins of an institution
+		#[pallet::constant]
+		type MaxInstitutionAdmins: Get<u32>;
+
+		/// Maximum bytes allowed for the title and the department of a position
+		#[pallet::constant]
+		type MaxPositionLen: Get<u32>;
+
+		/// Maximum number of job offers a student can have open at once
+		#[pallet::constant]
+		type MaxJobOffers: Get<u32>;
+
+		/// Maximum number of those job offers coming from a single parachain
+		#[pallet::constant]
+		type MaxJobOffersPerPara: Get<u32>;
+
+		/// Registers the queries answered by the source chain of a student, see
+		/// [`Pallet::request_verification`].
+		type Queries: NotifyQuery<<Self as Config>::RuntimeCall, BlockNumberFor<Self>>;
+
+		/// Origin of the responses to those queries, resolving to the responder.
+		type ResponseOrigin: EnsureOrigin<
+			<Self as frame_system::Config>::RuntimeOrigin,
+			Success = Location,
+		>;
+
+		/// Location of this chain, to tell the responder where to answer.
+		type UniversalLocation: Get<InteriorLocation>;
+
+		/// Number of blocks to wait for the answer to a verification request
+		#[pallet::constant]
+		type VerificationTimeout: Get<BlockNumberFor<Self>>;
+
+		/// Maximum number of unanswered verification requests of a student
+		#[pallet::constant]
+		type MaxPendingVerifications: Get<u32>;
+
+		/// Maximum number of verification requests timing out in the same block
+		#[pallet::constant]
+		type MaxVerificationsPerBlock: Get<u32>;
+
+		/// Items representing the students, e.g. in `pallet_nfts`.
+		type Items: StudentItems<Self::AccountId>;
+
+		/// Currency of the scholarship fund. Its native token is reserve transferred to pay
+		/// graduates on other chains.
+		type Currency: fungible::Mutate<Self::AccountId>;
+
+		/// Id of the accounts institutions collect tuition in and pay stipends from, see
+		/// [`Pallet::institution_account`]
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Number of blocks between two payments of a stipend
+		#[pallet::constant]
+		type StipendPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Maximum number of stipends paid in the same block
+		#[pallet::constant]
+		type MaxStipendsPerBlock: Get<u32>;
+
+		/// Weight of sending the reserve transfer of a stipend paid to a graduate on another chain
+		#[pallet::constant]
+		type StipendSendWeight: Get<Weight>;
+
+		/// Maximum number of unpaid tuition invoices of a student
+		#[pallet::constant]
+		type MaxInvoicesPerStudent: Get<u32>;
+
+		/// Maximum number of faculty members on the graduation committee of an institution
+		#[pallet::constant]
+		type MaxCommitteeMembers: Get<u32>;
+
+		/// Number of blocks a graduation committee has to approve a graduation
+		#[pallet::constant]
+		type GraduationApprovalPeriod: Get<BlockNumberFor<Self>>;
+	}
+
+	pub type BalanceOf<T> = <<T as Config>::Currency as fungible::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	/// Registers XCM queries whose response is dispatched as a call, e.g. the notify queries of
+	/// `pallet_xcm`. The query id and the response are appended to the arguments of `notify`.
+	pub trait NotifyQuery<Call, BlockNumber> {
+		fn new_notify_query(responder: Location, notify: Call, timeout: BlockNumber) -> QueryId;
+	}
+
+	/// Non-fungible items representing students, in one collection per institution. The item of a
+	/// student has the id of the student and holds the encoded record as metadata.
+	pub trait StudentItems<AccountId> {
+		/// Create the collection of a new institution.
+		fn create_collection(institution_id: u32) -> DispatchResult;
+
+		fn mint(
+			institution_id: u32,
+			student_id: u32,
+			owner: &AccountId,
+			metadata: &[u8],
+		) -> DispatchResult;
+
+		fn set_metadata(institution_id: u32, student_id: u32, metadata: &[u8]) -> DispatchResult;
+
+		fn transfer(institution_id: u32, student_id: u32, to: &AccountId) -> DispatchResult;
+
+		fn burn(institution_id: u32, student_id: u32) -> DispatchResult;
+
+		fn owner(institution_id: u32, student_id: u32) -> Option<AccountId>;
+
+		/// The item as an XCM asset, as seen from this chain.
+		fn asset(institution_id: u32, student_id: u32) -> Asset;
 	}
 
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	
@@ -70,335 +257,3488 @@ pub mod pallet {
 		Male,
 		Female,
 		Other,
+		/// Not disclosed.
+		Undisclosed,
 	}
 
 
-	// Defining Student structure
+	/// Personal data of a student as submitted in calls, see [`PersonalData`].
+	#[derive(Encode, Decode, DecodeWithMemTracking, TypeInfo, Clone, PartialEq, Eq, Debug)]
+	pub struct PersonalDataInput<Hash> {
+		pub given_name: Vec<u8>,
+		pub middle_name: Vec<u8>,
+		pub family_name: Vec<u8>,
+		pub date_of_birth: Date,
+		pub gender: Gender,
+		pub national_id_hash: Option<Hash>,
+		pub email_hash: Option<Hash>,
+	}
+
+	/// Validated personal data of a student.
 	#[derive(
-		Encode, Decode, MaxEncodedLen,  DecodeWithMemTracking, TypeInfo,
-		CloneNoBound, PartialEqNoBound, DebugNoBound, Default,
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		DecodeWithMemTracking,
+		TypeInfo,
+		CloneNoBound,
+		PartialEqNoBound,
+		DebugNoBound,
 	)]
 	#[scale_info(skip_type_params(T))]
-	pub struct Student<T: Config> {
-		pub name: BoundedVec<u8, T::MaxNameLen>,
-		pub surname: BoundedVec<u8, T::MaxSurnameLen>,
-		pub age: u32,
+	pub struct PersonalData<T: Config> {
+		/// UTF-8, not empty.
+		pub given_name: BoundedVec<u8, T::MaxNameLen>,
+		/// UTF-8, empty if the student has none.
+		pub middle_name: BoundedVec<u8, T::MaxNameLen>,
+		/// UTF-8, not empty.
+		pub family_name: BoundedVec<u8, T::MaxSurnameLen>,
+		pub date_of_birth: Date,
 		pub gender: Gender,
-		pub has_graduated: bool,
+		/// Hash of the national identification number, if provided.
+		pub national_id_hash: Option<T::Hash>,
+		/// Hash of the e-mail address, if provided.
+		pub email_hash: Option<T::Hash>,
 	}
 
+	impl<T: Config> From<PersonalData<T>> for PersonalDataInput<T::Hash> {
+		fn from(personal: PersonalData<T>) -> Self {
+			PersonalDataInput {
+				given_name: personal.given_name.into_inner(),
+				middle_name: personal.middle_name.into_inner(),
+				family_name: personal.family_name.into_inner(),
+				date_of_birth: personal.date_of_birth,
+				gender: personal.gender,
+				national_id_hash: personal.national_id_hash,
+				email_hash: personal.email_hash,
+			}
+		}
+	}
 
-	/// Stores a Student for each account
-	#[pallet::storage]
-	pub type StudentCount<T> = StorageValue<_, u32, ValueQuery>;
-
-	#[pallet::storage]
-	pub type Students<T: Config> =
-		StorageMap<_, Blake2_128Concat, u32, Student<T>, OptionQuery>;
+	/// Set of fields of [`PersonalData`], one bit per field.
+	#[derive(
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		DecodeWithMemTracking,
+		TypeInfo,
+		Clone,
+		Copy,
+		PartialEq,
+		Eq,
+		Debug,
+		Default,
+	)]
+	pub struct PersonalDataFields(pub u8);
+
+	impl PersonalDataFields {
+		pub const GIVEN_NAME: Self = Self(1);
+		pub const MIDDLE_NAME: Self = Self(1 << 1);
+		pub const FAMILY_NAME: Self = Self(1 << 2);
+		pub const DATE_OF_BIRTH: Self = Self(1 << 3);
+		pub const GENDER: Self = Self(1 << 4);
+		pub const NATIONAL_ID_HASH: Self = Self(1 << 5);
+		pub const EMAIL_HASH: Self = Self(1 << 6);
+
+		pub fn contains(self, fields: Self) -> bool {
+			self.0 & fields.0 == fields.0
+		}
 
-	#[pallet::storage]
-	pub type StudentsByOwner<T: Config> = StorageMap<
-		_,
-		Blake2_128Concat,
-		T::AccountId,
-		BoundedVec<u32, ConstU32<100>>, // max 100 students per account
-		ValueQuery,
-	>;
+		pub fn union(self, fields: Self) -> Self {
+			Self(self.0 | fields.0)
+		}
 
+		pub fn is_empty(self) -> bool {
+			self.0 == 0
+		}
 
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		SomethingStored { block_number: BlockNumberFor<T>, who: T::AccountId },
+		/// Fields that differ between `a` and `b`.
+		pub fn changed<T: Config>(a: &PersonalData<T>, b: &PersonalData<T>) -> Self {
+			[
+				(a.given_name != b.given_name, Self::GIVEN_NAME),
+				(a.middle_name != b.middle_name, Self::MIDDLE_NAME),
+				(a.family_name != b.family_name, Self::FAMILY_NAME),
+				(a.date_of_birth != b.date_of_birth, Self::DATE_OF_BIRTH),
+				(a.gender != b.gender, Self::GENDER),
+				(a.national_id_hash != b.national_id_hash, Self::NATIONAL_ID_HASH),
+				(a.email_hash != b.email_hash, Self::EMAIL_HASH),
+			]
+			.into_iter()
+			.filter(|(changed, _)| *changed)
+			.fold(Self::default(), |fields, (_, field)| fields.union(field))
+		}
+	}
 
-		StudentCreated { who: T::AccountId },
-		StudentDeleted { who: T::AccountId, student_id: u32 },
+	/// Change to the personal data of a student, `None` for the fields left as they are.
+	#[derive(
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		DecodeWithMemTracking,
+		TypeInfo,
+		CloneNoBound,
+		PartialEqNoBound,
+		DebugNoBound,
+		DefaultNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct PersonalDataDelta<T: Config> {
+		pub given_name: Option<BoundedVec<u8, T::MaxNameLen>>,
+		pub middle_name: Option<BoundedVec<u8, T::MaxNameLen>>,
+		pub family_name: Option<BoundedVec<u8, T::MaxSurnameLen>>,
+		pub date_of_birth: Option<Date>,
+		pub gender: Option<Gender>,
+		pub national_id_hash: Option<Option<T::Hash>>,
+		pub email_hash: Option<Option<T::Hash>>,
+	}
 
-		XcmMessageSent { destination: Location },
-		StudentReceived { student_id: u32 },
-		StudentTransferred { student_id: u32, destination: Location },
-		StudentGraduatedAndTransferred { who: T::AccountId, student_id: u32, destination: Location },
-		StudentUpdated { who: T::AccountId, student_id: u32 },
-		StudentDeletedByAdmin { student_id: u32 },
+	impl<T: Config> PersonalDataDelta<T> {
+		/// Apply the change to `personal`, except for the fields in `kept`. Returns the fields
+		/// applied and the fields skipped.
+		pub fn apply_to(
+			self,
+			personal: &mut PersonalData<T>,
+			kept: PersonalDataFields,
+		) -> (PersonalDataFields, PersonalDataFields) {
+			let mut applied = PersonalDataFields::default();
+			let mut skipped = PersonalDataFields::default();
+			let mut take = |field: PersonalDataFields| {
+				if kept.contains(field) {
+					skipped = skipped.union(field);
+					false
+				} else {
+					applied = applied.union(field);
+					true
+				}
+			};
+			if let Some(value) = self.given_name.filter(|_| take(PersonalDataFields::GIVEN_NAME)) {
+				personal.given_name = value;
+			}
+			if let Some(value) = self.middle_name.filter(|_| take(PersonalDataFields::MIDDLE_NAME))
+			{
+				personal.middle_name = value;
+			}
+			if let Some(value) = self.family_name.filter(|_| take(PersonalDataFields::FAMILY_NAME))
+			{
+				personal.family_name = value;
+			}
+			if let Some(value) =
+				self.date_of_birth.filter(|_| take(PersonalDataFields::DATE_OF_BIRTH))
+			{
+				personal.date_of_birth = value;
+			}
+			if let Some(value) = self.gender.filter(|_| take(PersonalDataFields::GENDER)) {
+				personal.gender = value;
+			}
+			if let Some(value) =
+				self.national_id_hash.filter(|_| take(PersonalDataFields::NATIONAL_ID_HASH))
+			{
+				personal.national_id_hash = value;
+			}
+			if let Some(value) = self.email_hash.filter(|_| take(PersonalDataFields::EMAIL_HASH)) {
+				personal.email_hash = value;
+			}
+			(applied, skipped)
+		}
 	}
 
+	/// Account that receives a graduated student on the destination chain.
+	#[derive(
+		Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, Debug,
+	)]
+	pub enum Beneficiary {
+		/// Any location, interpreted relative to the destination chain.
+		Location(Location),
+		/// A 32-byte account on the destination chain.
+		AccountId32([u8; 32]),
+		/// A 20-byte (Ethereum-style) account on the destination chain.
+		AccountKey20([u8; 20]),
+	}
 
-	#[pallet::error]
-	pub enum Error<T> {
-		NoneValue,
-		StorageOverflow,
-		NameTooLong,
-		SurnameTooLong,
-		StudentAlreadyExists,
-		MaxStudentsReached,
-		StudentNotFound,
-		NotStudentOwner,
-		AlreadyGraduated,
-		XcmSendFailed,
+	impl From<Beneficiary> for Location {
+		fn from(beneficiary: Beneficiary) -> Self {
+			match beneficiary {
+				Beneficiary::Location(location) => location,
+				Beneficiary::AccountId32(id) => AccountId32 { network: None, id }.into(),
+				Beneficiary::AccountKey20(key) => AccountKey20 { network: None, key }.into(),
+			}
+		}
 	}
 
-	
-	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	/// Salted hash of a student's personal data, see [`Pallet::commitment_of`], together with a
+	/// reference to where the plaintext is kept off-chain.
+	#[derive(
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		DecodeWithMemTracking,
+		TypeInfo,
+		CloneNoBound,
+		PartialEqNoBound,
+		DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct PiiCommitment<T: Config> {
+		pub hash: T::Hash,
+		pub reference: BoundedVec<u8, T::MaxReferenceLen>,
+	}
 
-	
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
+	/// Stage of a student's lifecycle, see [`StudentStatus::can_become`] for the allowed changes.
+	#[derive(
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		DecodeWithMemTracking,
+		TypeInfo,
+		Clone,
+		Copy,
+		PartialEq,
+		Eq,
+		Debug,
+		Default,
+	)]
+	pub enum StudentStatus {
+		#[default]
+		Enrolled,
+		Suspended,
+		OnLeave,
+		Withdrawn,
+		Graduated,
+		/// Graduated and moved to another chain.
+		Transferred,
+		Employed,
+	}
 
-		// CREATE STUDENT
-		#[pallet::call_index(0)]
-		#[pallet::weight(10_000)]
-		pub fn create_student(
-			origin: OriginFor<T>,
-			name: Vec<u8>,
-			surname: Vec<u8>,
-			age: u32,
-			gender: Gender,
-		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+	impl StudentStatus {
+		/// Whether a student in this status may move to `next`.
+		pub fn can_become(self, next: Self) -> bool {
+			use StudentStatus::*;
+			matches!(
+				(self, next),
+				(Enrolled, Suspended | OnLeave | Withdrawn | Graduated) |
+					(Suspended | OnLeave, Enrolled | Withdrawn) |
+					(Withdrawn, Enrolled) |
+					(Graduated, Transferred | Employed) |
+					(Transferred, Employed) |
+					(Employed, Graduated)
+			)
+		}
 
-			// Convert name → bounded
-			let name: BoundedVec<_, T::MaxNameLen> =
-				name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+		/// Whether the student has finished their studies.
+		pub fn has_graduated(self) -> bool {
+			matches!(self, Self::Graduated | Self::Transferred | Self::Employed)
+		}
+	}
 
-			let surname: BoundedVec<_, T::MaxSurnameLen> =
-				surname.try_into().map_err(|_| Error::<T>::SurnameTooLong)?;
+	// Defining Student structure
+	#[derive(
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		DecodeWithMemTracking,
+		TypeInfo,
+		CloneNoBound,
+		PartialEqNoBound,
+		DebugNoBound,
+		DefaultNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Student<T: Config> {
+		/// `None` when only committed to, or erased.
+		pub personal: Option<PersonalData<T>>,
+		pub status: StudentStatus,
+		/// Unix time in seconds of the last status change.
+		pub status_since: u64,
+		/// Set when the personal data is only committed to instead of stored in clear.
+		pub commitment: Option<PiiCommitment<T>>,
+	}
 
-			// Generate new student ID
-			let student_id = StudentCount::<T>::get();
-			StudentCount::<T>::put(student_id + 1);
+	impl<T: Config> Student<T> {
+		/// Wipe the plaintext personal data, keeping the rest of the record for auditing.
+		pub fn erase_personal_data(&mut self) {
+			self.personal = None;
+		}
+	}
 
-			// Build student struct
-			let student = Student::<T> {
-				name,
-				surname,
-				age,
-				gender,
-				has_graduated: false,
-			};
+	/// Where a student received from a sibling chain came from.
+	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, Debug)]
+	pub struct StudentOrigin {
+		/// Parachain that sent the student.
+		pub para_id: u32,
+		/// Id of the student on the sending parachain.
+		pub student_id: u32,
+	}
 
-			// Insert into Students map
-			Students::<T>::insert(student_id, student);
+	/// Kind of change recorded in a student's history.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo, Clone, PartialEq, Eq, Debug,
+	)]
+	pub enum Change {
+		Created,
+		Updated,
+		StatusChanged {
+			from: StudentStatus,
+			to: StudentStatus,
+		},
+		Erased,
+		/// Sent to another chain.
+		Sent {
+			destination_para: u32,
+		},
+		/// Received from another chain, where it had the id `source_id`.
+		Received {
+			source_para: u32,
+			source_id: u32,
+		},
+		Deleted,
+		Restored,
+		/// Moved to another position by the employer.
+		Promoted,
+		/// Diploma revoked by the chain that awarded it.
+		Revoked,
+	}
 
-			// Add student's ID into student's owner list
-			StudentsByOwner::<T>::try_mutate(&who, |list| {
-				list.try_push(student_id)
-					.map_err(|_| Error::<T>::MaxStudentsReached)
-			})?;
+	/// Entry of a student's history.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo, Clone, PartialEq, Eq, Debug,
+	)]
+	pub struct HistoryEntry<Hash> {
+		/// Unix time in seconds of the change, on the chain that made it.
+		pub at: u64,
+		pub change: Change,
+		/// Hash of the record before the change, `None` when there was none on this chain.
+		pub previous: Option<Hash>,
+	}
 
-			Ok(())
-		}
+	pub type HistoryOf<T> =
+		BoundedVec<HistoryEntry<<T as frame_system::Config>::Hash>, <T as Config>::MaxHistoryLen>;
+
+	/// What remains of a student after graduating to another chain.
+	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, Debug)]
+	pub struct AlumniRecord<AccountId, BlockNumber, Hash> {
+		/// Parachain the student was sent to.
+		pub destination_para: u32,
+		/// Institution that enrolled the student, whose collection holds the reserved item.
+		pub institution_id: Option<u32>,
+		/// Owner that graduated the student.
+		pub graduated_by: AccountId,
+		/// Block of the graduation.
+		pub graduated_at: BlockNumber,
+		/// Hash of the record as sent to the destination, see [`Pallet::diploma_hash`].
+		pub diploma_hash: Hash,
+	}
 
+	pub type AlumniRecordOf<T> = AlumniRecord<
+		<T as frame_system::Config>::AccountId,
+		BlockNumberFor<T>,
+		<T as frame_system::Config>::Hash,
+	>;
 
-		// GRADUATE STUDENT
-		#[pallet::call_index(1)]
-		#[pallet::weight(10_000)]
-		pub fn graduate_student(
-			origin: OriginFor<T>,
-			student_id: u32,
-		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+	/// Student waiting to be purged, see [`Config::DeletionGracePeriod`].
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct DeletedStudent<T: Config> {
+		pub student: Student<T>,
+		/// Owner to give the student back to on restore, `None` if it had none.
+		pub owner: Option<T::AccountId>,
+		/// Block from which the student can no longer be restored.
+		pub purge_at: BlockNumberFor<T>,
+	}
 
-			// Ensure the caller owns this student
-			let owned_ids = StudentsByOwner::<T>::get(&who);
-			ensure!(owned_ids.contains(&student_id), Error::<T>::NotStudentOwner);
+	/// Graduation transfer waiting for the student's approval.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct PendingTransfer<T: Config> {
+		/// Owner that requested the transfer.
+		pub requested_by: T::AccountId,
+		/// Beneficiary on the destination, if not the owner.
+		pub beneficiary: Option<Location>,
+		/// Last block in which the student can approve.
+		pub expires_at: BlockNumberFor<T>,
+	}
 
-			// Get the student and ensure they exist
-			let mut student = Students::<T>::get(student_id)
-				.ok_or(Error::<T>::StudentNotFound)?;
+	/// Accreditation of an institution. Only accredited institutions can enrol and graduate
+	/// students.
+	#[derive(
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		DecodeWithMemTracking,
+		TypeInfo,
+		Clone,
+		Copy,
+		PartialEq,
+		Eq,
+		Debug,
+		Default,
+	)]
+	pub enum Accreditation {
+		#[default]
+		Pending,
+		Accredited,
+		Suspended,
+		Revoked,
+	}
 
-			// Ensure student is not already graduated
-			ensure!(!student.has_graduated, Error::<T>::AlreadyGraduated);
+	/// Where an institution's graduates are sent.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo, Clone, PartialEq, Eq, Debug,
+	)]
+	pub struct GraduationDestination {
+		pub para_id: u32,
+		/// Institution on the destination chain, holding the copy of the student's item.
+		pub institution: u32,
+	}
 
-			// Mark as graduated
-			student.has_graduated = true;
+	/// Institution hosted on this chain.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Institution<T: Config> {
+		pub name: BoundedVec<u8, T::MaxInstitutionNameLen>,
+		/// Accounts that manage the institution and its students.
+		pub admins: BoundedVec<T::AccountId, T::MaxInstitutionAdmins>,
+		pub accreditation: Accreditation,
+		/// `None` to use [`Config::GraduationDestinationPara`] and
+		/// [`Config::GraduationDestinationInstitution`].
+		pub graduation_destination: Option<GraduationDestination>,
+	}
 
-			// Prepare XCM transfer to destination parachain
-			let dest_para_id = T::GraduationDestinationPara::get();
-			let destination = Location::new(1, [Parachain(dest_para_id)]);
+	/// Kind of employment contract.
+	#[derive(
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		DecodeWithMemTracking,
+		TypeInfo,
+		Clone,
+		Copy,
+		PartialEq,
+		Eq,
+		Debug,
+		Default,
+	)]
+	pub enum ContractType {
+		#[default]
+		Permanent,
+		FixedTerm,
+		PartTime,
+		Internship,
+	}
 
-			// Encode the receive_student call WITH the owner
-			let call = <T as Config>::RuntimeCall::from(
-				Call::<T>::receive_student { 
-					student: student.clone(),
-					new_owner: who.clone() // Pass the current owner
-				}
-			).encode();
+	/// Position held by an employee.
+	#[derive(
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		DecodeWithMemTracking,
+		TypeInfo,
+		CloneNoBound,
+		PartialEqNoBound,
+		DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Position<T: Config> {
+		pub title: BoundedVec<u8, T::MaxPositionLen>,
+		pub department: BoundedVec<u8, T::MaxPositionLen>,
+		pub contract: ContractType,
+	}
 
-			// Build XCM message
-			let message = Xcm(vec![
-				UnpaidExecution {
-					weight_limit: WeightLimit::Unlimited,
-					check_origin: None,
-				},
-				Transact {
-					origin_kind: OriginKind::SovereignAccount,
-					fallback_max_weight: Some(Weight::from_parts(1_000_000_000, 64 * 1024)),
-					call: call.into(),
-				},
-			]);
+	/// Current job of a graduate received from another chain.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Employment<T: Config> {
+		/// Institution the graduate works for.
+		pub employer: u32,
+		pub position: Position<T>,
+		/// Block of the hiring.
+		pub start: BlockNumberFor<T>,
+		/// Block of the latest promotion, if any.
+		pub promoted_at: Option<BlockNumberFor<T>>,
+	}
 
-			// Send XCM message
-			polkadot_sdk::staging_xcm::latest::send_xcm::<T::XcmSender>(
-				destination.clone(),
-				message,
-			)
-			.map_err(|_| Error::<T>::XcmSendFailed)?;
+	/// Job offered to a student by an institution on a sibling chain.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct JobOffer<T: Config> {
+		/// Parachain of the employer, where the student goes on acceptance.
+		pub para_id: u32,
+		/// Institution making the offer, on that parachain.
+		pub employer: u32,
+		pub position: Position<T>,
+		/// Block the offer was received in.
+		pub received_at: BlockNumberFor<T>,
+	}
 
-			// Remove student from this parachain after successful transfer
-			StudentsByOwner::<T>::try_mutate(&who, |owned_ids| {
-				if let Some(index) = owned_ids.iter().position(|id| *id == student_id) {
-					owned_ids.swap_remove(index);
-					Ok(())
-				} else {
-					Err(Error::<T>::NotStudentOwner)
-				}
-			})?;
+	/// Answer of the source chain to a verification request.
+	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, Debug)]
+	pub struct Verification<BlockNumber> {
+		/// Whether the source chain still vouches for the diploma.
+		pub valid: bool,
+		/// Block the answer was received in.
+		pub at: BlockNumber,
+	}
 
-			Students::<T>::remove(student_id);
+	/// Where a stipend is paid.
+	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, Debug)]
+	pub enum StipendPayee {
+		/// Account linked to the student on this chain at the time of each payment, see
+		/// [`StudentAccounts`].
+		Student,
+		/// Account of a graduate on the chain they graduated to, paid with a reserve transfer.
+		Graduate { para_id: u32, beneficiary: Location },
+	}
 
-			Self::deposit_event(Event::StudentGraduatedAndTransferred { 
-				who, 
-				student_id, 
-				destination 
-			});
+	/// Stipend awarded to a student, paid from the scholarship fund of its institution every
+	/// [`Config::StipendPeriod`].
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Stipend<T: Config> {
+		/// Institution awarding the stipend, whose admins can revoke it.
+		pub institution: u32,
+		pub amount: BalanceOf<T>,
+		pub payee: StipendPayee,
+		/// Whether payments continue on the graduation destination after graduation.
+		pub after_graduation: bool,
+		/// Block of the next payment.
+		pub next_payment: BlockNumberFor<T>,
+	}
 
-			Ok(())
-		}
+	/// Tuition owed by a student to its institution, see [`Pallet::issue_invoice`].
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Invoice<T: Config> {
+		pub id: u32,
+		/// Institution the tuition is paid to.
+		pub institution: u32,
+		pub amount: BalanceOf<T>,
+		/// Block the invoice is due by.
+		pub due: BlockNumberFor<T>,
+	}
+
+	/// Faculty members of an institution approving its graduations, see
+	/// [`Pallet::set_graduation_committee`].
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Committee<T: Config> {
+		pub members: BoundedVec<T::AccountId, T::MaxCommitteeMembers>,
+		/// Number of approvals a graduation needs.
+		pub threshold: u32,
+	}
+
+	/// Graduation waiting for the approval of the committee of the student's institution.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct PendingGraduation<T: Config> {
+		/// Owner the student is sent for.
+		pub owner: T::AccountId,
+		/// Beneficiary on the destination, if not the owner.
+		pub beneficiary: Option<Location>,
+		pub destination: GraduationDestination,
+		/// Committee members that approved so far.
+		pub approvals: BoundedVec<T::AccountId, T::MaxCommitteeMembers>,
+		/// Last block in which the committee can approve.
+		pub expires_at: BlockNumberFor<T>,
+	}
+
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	/// Stores a Student for each account
+	#[pallet::storage]
+	pub type StudentCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Students<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, Student<T>, OptionQuery>;
+
+	/// Students of each owner, see [`Pallet::students_of`]
+	#[pallet::storage]
+	pub type StudentsByOwner<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, u32, (), OptionQuery>;
+
+	/// Number of students of each owner, at most [`Config::MaxStudentsPerOwner`]
+	#[pallet::storage]
+	pub type OwnedStudentCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Owner of each student
+	#[pallet::storage]
+	pub type StudentOwner<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, T::AccountId, OptionQuery>;
+
+	/// Account of the student themselves, who has to approve graduation transfers
+	#[pallet::storage]
+	pub type StudentAccounts<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, T::AccountId, OptionQuery>;
+
+	/// Graduation transfers waiting for the student's approval
+	#[pallet::storage]
+	pub type PendingTransfers<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, PendingTransfer<T>, OptionQuery>;
+
+	/// Deleted students that can still be restored
+	#[pallet::storage]
+	pub type DeletedStudents<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, DeletedStudent<T>, OptionQuery>;
+
+	/// Deleted students by the block they are to be purged in
+	#[pallet::storage]
+	pub type PurgeAgenda<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<u32, T::MaxDeletionsPerBlock>,
+		ValueQuery,
+	>;
+
+	/// First block of [`PurgeAgenda`] not fully processed yet, zero before the first purge
+	#[pallet::storage]
+	pub type NextPurgeBlock<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	/// Latest changes of each student, oldest first, including those made on previous chains
+	#[pallet::storage]
+	pub type StudentHistory<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, HistoryOf<T>, ValueQuery>;
+
+	/// Students that graduated to another chain, by their former id here
+	#[pallet::storage]
+	pub type Alumni<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, AlumniRecordOf<T>, OptionQuery>;
+
+	/// Origin of students received from sibling chains
+	#[pallet::storage]
+	pub type ReceivedFrom<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, StudentOrigin, OptionQuery>;
+
+	/// Local id of received students, by sending parachain and id on that parachain
+	#[pallet::storage]
+	pub type ReceivedIndex<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Blake2_128Concat, u32, u32, OptionQuery>;
+
+	/// Students announced by the chain sending them, with the account below that chain sending
+	/// them, see [`Pallet::expect_student`]
+	#[pallet::storage]
+	pub type ExpectedStudents<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Blake2_128Concat,
+		u32,
+		InteriorLocation,
+		OptionQuery,
+	>;
+
+	/// Student id by identity, see [`Pallet::identity_of`]
+	#[pallet::storage]
+	pub type StudentIdentities<T: Config> = StorageMap<_, Identity, T::Hash, u32, OptionQuery>;
+
+	/// Id of the next institution to register
+	#[pallet::storage]
+	pub type InstitutionCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Institutions<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, Institution<T>, OptionQuery>;
+
+	/// Institution each student is enrolled in. Students created before institutions existed,
+	/// or received without one, have none.
+	#[pallet::storage]
+	pub type StudentInstitution<T: Config> = StorageMap<_, Blake2_128Concat, u32, u32, OptionQuery>;
+
+	/// Current employment of each employed graduate
+	#[pallet::storage]
+	pub type Employments<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, Employment<T>, OptionQuery>;
+
+	/// Open job offers of each student, at most one per employer
+	#[pallet::storage]
+	pub type JobOffers<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, BoundedVec<JobOffer<T>, T::MaxJobOffers>, ValueQuery>;
+
+	/// Hash of the record of each received student as sent by the source chain, see
+	/// [`Pallet::diploma_hash`]
+	#[pallet::storage]
+	pub type ReceivedDiplomas<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, T::Hash, OptionQuery>;
+
+	/// Student each unanswered verification request is about
+	#[pallet::storage]
+	pub type PendingVerifications<T: Config> =
+		StorageMap<_, Twox64Concat, QueryId, u32, OptionQuery>;
+
+	/// Number of unanswered verification requests of each student
+	#[pallet::storage]
+	pub type PendingVerificationCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, u32, ValueQuery>;
+
+	/// Verification requests by the block they are dropped in, once their answer is late
+	#[pallet::storage]
+	pub type VerificationAgenda<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<QueryId, T::MaxVerificationsPerBlock>,
+		ValueQuery,
+	>;
+
+	/// First block of [`VerificationAgenda`] not fully processed yet, zero before the first visit
+	#[pallet::storage]
+	pub type NextVerificationExpiry<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	/// Latest verification of each received student
+	#[pallet::storage]
+	pub type Verifications<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, Verification<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Revoked diplomas and the block of the revocation, by the former id of the alumnus on the
+	/// awarding chain, or by the id of the received student on the chains holding a copy
+	#[pallet::storage]
+	pub type RevokedDiplomas<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, BlockNumberFor<T>, OptionQuery>;
+
+	/// Alumni whose destination subscribed to updates of the record, see
+	/// [`Pallet::amend_alumnus`]
+	#[pallet::storage]
+	pub type UpdateSubscriptions<T: Config> = StorageMap<_, Blake2_128Concat, u32, (), OptionQuery>;
+
+	/// Fields of received students changed on this chain. They belong to this chain from then
+	/// on, and updates from the source chain leave them as they are.
+	#[pallet::storage]
+	pub type LocalEdits<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, PersonalDataFields, ValueQuery>;
+
+	/// Stipend of each student, see [`Pallet::award_stipend`]
+	#[pallet::storage]
+	pub type Stipends<T: Config> = StorageMap<_, Blake2_128Concat, u32, Stipend<T>, OptionQuery>;
+
+	/// Students with a stipend by the block of their next payment
+	#[pallet::storage]
+	pub type StipendAgenda<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<u32, T::MaxStipendsPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	pub type InvoiceCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Unpaid tuition invoices of each student, see [`Pallet::issue_invoice`]
+	#[pallet::storage]
+	pub type Invoices<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		BoundedVec<Invoice<T>, T::MaxInvoicesPerStudent>,
+		ValueQuery,
+	>;
+
+	/// Graduation committee of each institution that has one
+	#[pallet::storage]
+	pub type GraduationCommittees<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, Committee<T>, OptionQuery>;
+
+	/// Graduations waiting for the approval of a committee
+	#[pallet::storage]
+	pub type PendingGraduations<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, PendingGraduation<T>, OptionQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// Accredited institutions, by name and admins.
+		pub institutions: Vec<(Vec<u8>, Vec<T::AccountId>)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (name, admins) in &self.institutions {
+				let id = InstitutionCount::<T>::get();
+				Institutions::<T>::insert(
+					id,
+					Institution::<T> {
+						name: name.clone().try_into().expect("institution name too long"),
+						admins: admins.clone().try_into().expect("too many institution admins"),
+						accreditation: Accreditation::Accredited,
+						graduation_destination: None,
+					},
+				);
+				T::Items::create_collection(id).expect("collection of the institution");
+				InstitutionCount::<T>::put(id + 1);
+			}
+		}
+	}
+
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		SomethingStored { block_number: BlockNumberFor<T>, who: T::AccountId },
+
+		StudentCreated {
+			who: T::AccountId,
+		},
+		StudentDeleted {
+			who: T::AccountId,
+			student_id: u32,
+			purge_at: BlockNumberFor<T>,
+		},
+
+		XcmMessageSent {
+			destination: Location,
+		},
+		StudentReceived {
+			student_id: u32,
+			owner: T::AccountId,
+		},
+		StudentTransferred {
+			student_id: u32,
+			destination: Location,
+		},
+		StudentGraduatedAndTransferred {
+			who: T::AccountId,
+			student_id: u32,
+			destination: Location,
+			beneficiary: Option<Location>,
+		},
+		StudentUpdated {
+			who: T::AccountId,
+			student_id: u32,
+			old: Option<PersonalData<T>>,
+			new: PersonalData<T>,
+		},
+		StudentDeletedByAdmin {
+			student_id: u32,
+			purge_at: BlockNumberFor<T>,
+		},
+		StudentDataVerified {
+			who: T::AccountId,
+			student_id: u32,
+		},
+		StudentDataErased {
+			student_id: u32,
+		},
+		StudentStatusChanged {
+			student_id: u32,
+			from: StudentStatus,
+			to: StudentStatus,
+			at: u64,
+		},
+		StudentAccountSet {
+			student_id: u32,
+			account: Option<T::AccountId>,
+		},
+		TransferRequested {
+			student_id: u32,
+			student_account: T::AccountId,
+			expires_at: BlockNumberFor<T>,
+		},
+		TransferApproved {
+			student_id: u32,
+			who: T::AccountId,
+		},
+		StudentRestored {
+			student_id: u32,
+		},
+		StudentPurged {
+			student_id: u32,
+		},
+		InstitutionRegistered {
+			institution_id: u32,
+		},
+		AccreditationChanged {
+			institution_id: u32,
+			accreditation: Accreditation,
+		},
+		InstitutionAdminsSet {
+			institution_id: u32,
+		},
+		GraduationDestinationSet {
+			institution_id: u32,
+			destination: Option<GraduationDestination>,
+		},
+		StudentHired {
+			student_id: u32,
+			employer: u32,
+			position: Position<T>,
+		},
+		StudentPromoted {
+			student_id: u32,
+			position: Position<T>,
+		},
+		EmploymentTerminated {
+			student_id: u32,
+			employer: u32,
+		},
+		JobOfferSent {
+			employer: u32,
+			destination: Location,
+			student_id: u32,
+		},
+		JobOfferReceived {
+			student_id: u32,
+			para_id: u32,
+			employer: u32,
+		},
+		JobOfferAccepted {
+			student_id: u32,
+			para_id: u32,
+			employer: u32,
+		},
+		JobOfferDeclined {
+			student_id: u32,
+			para_id: u32,
+			employer: u32,
+		},
+		VerificationRequested {
+			student_id: u32,
+			query_id: QueryId,
+		},
+		StudentVerified {
+			student_id: u32,
+			valid: bool,
+		},
+		VerificationExpired {
+			student_id: u32,
+			query_id: QueryId,
+		},
+		DiplomaRevoked {
+			student_id: u32,
+			destination: Location,
+		},
+		ReceivedDiplomaRevoked {
+			student_id: u32,
+			source_para: u32,
+		},
+		UpdateSubscriptionRequested {
+			student_id: u32,
+			subscribed: bool,
+		},
+		UpdateSubscriptionChanged {
+			student_id: u32,
+			para_id: u32,
+			subscribed: bool,
+		},
+		AlumnusAmended {
+			student_id: u32,
+			destination: Location,
+		},
+		StudentUpdatedFromSource {
+			student_id: u32,
+			applied: PersonalDataFields,
+			kept: PersonalDataFields,
+		},
+		ScholarshipFundToppedUp {
+			institution_id: u32,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		StipendAwarded {
+			student_id: u32,
+			amount: BalanceOf<T>,
+			first_payment: BlockNumberFor<T>,
+		},
+		/// `destination` is the chain a graduate is paid on, `None` for this one.
+		StipendPaid {
+			student_id: u32,
+			amount: BalanceOf<T>,
+			destination: Option<Location>,
+		},
+		/// The payment is retried in the next period.
+		StipendPaymentFailed {
+			student_id: u32,
+			error: DispatchError,
+		},
+		StipendRevoked {
+			student_id: u32,
+		},
+		/// The student left, without payments continuing after graduation.
+		StipendEnded {
+			student_id: u32,
+		},
+		InvoiceIssued {
+			student_id: u32,
+			invoice_id: u32,
+			amount: BalanceOf<T>,
+			due: BlockNumberFor<T>,
+		},
+		InvoicePaid {
+			student_id: u32,
+			invoice_id: u32,
+			who: T::AccountId,
+		},
+		InvoiceCancelled {
+			student_id: u32,
+			invoice_id: u32,
+		},
+		/// `None` when the institution no longer has a committee.
+		GraduationCommitteeSet {
+			institution_id: u32,
+			threshold: Option<u32>,
+		},
+		GraduationApprovalRequested {
+			student_id: u32,
+			expires_at: BlockNumberFor<T>,
+		},
+		GraduationApproved {
+			student_id: u32,
+			who: T::AccountId,
+			approvals: u32,
+		},
+	}
+
+
+	#[pallet::error]
+	pub enum Error<T> {
+		NoneValue,
+		StorageOverflow,
+		NameTooLong,
+		SurnameTooLong,
+		EmptyName,
+		EmptySurname,
+		NameNotUtf8,
+		SurnameNotUtf8,
+		InvalidDateOfBirth,
+		DateOfBirthInFuture,
+		DateOfBirthTooOld,
+		StudentAlreadyExists,
+		/// The sending chain did not announce the student as sent by this account, see
+		/// [`Pallet::expect_student`].
+		StudentNotAnnounced,
+		MaxStudentsReached,
+		StudentNotFound,
+		NotStudentOwner,
+		AlreadyGraduated,
+		InvalidStatusTransition,
+		XcmSendFailed,
+		AccountNotConvertible,
+		BeneficiaryNotConvertible,
+		ReferenceTooLong,
+		NoCommitment,
+		CommitmentMismatch,
+		/// The call has to come from a sibling chain itself, not from an account on it.
+		NotFromSiblingChain,
+		NotStudentAccount,
+		NoPendingTransfer,
+		TransferAlreadyPending,
+		TransferExpired,
+		StudentNotDeleted,
+		RestoreWindowExpired,
+		TooManyDeletions,
+		InstitutionNotFound,
+		InstitutionNameTooLong,
+		TooManyInstitutionAdmins,
+		NotInstitutionAdmin,
+		NotAccredited,
+		PositionTooLong,
+		NotReceivedStudent,
+		NotEmployed,
+		/// The employment is managed by the employer, see [`Pallet::terminate_employment`].
+		HasEmployment,
+		TooManyJobOffers,
+		/// The sending parachain already has [`Config::MaxJobOffersPerPara`] offers open.
+		TooManyJobOffersFromPara,
+		NoJobOffer,
+		NotAlumnus,
+		DiplomaMismatch,
+		UnknownQuery,
+		/// The student already has [`Config::MaxPendingVerifications`] unanswered requests.
+		TooManyPendingVerifications,
+		TooManyVerifications,
+		DiplomaRevoked,
+		NotSubscribed,
+		/// Stipends are paid to the account of the student, see [`Pallet::set_student_account`].
+		NoStudentAccount,
+		StipendAlreadyAwarded,
+		NoStipend,
+		TooManyStipends,
+		TooManyInvoices,
+		NoInvoice,
+		/// Students graduate once all their tuition invoices are paid, see
+		/// [`Pallet::pay_invoice`].
+		TuitionUnpaid,
+		TooManyCommitteeMembers,
+		DuplicateCommitteeMember,
+		/// The threshold must be between one and the number of members.
+		InvalidThreshold,
+		NotCommitteeMember,
+		GraduationAlreadyPending,
+		NoPendingGraduation,
+		GraduationExpired,
+		AlreadyApproved,
+	}
+
+	
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			Self::pay_stipends(now)
+		}
+
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let used = Self::purge_expired(now, remaining_weight);
+			used.saturating_add(Self::expire_verifications(
+				now,
+				remaining_weight.saturating_sub(used),
+			))
+		}
+	}
+
+
+	#[pallet::view_functions_experimental]
+	impl<T: Config> Pallet<T> {
+		/// Age in full years of a student whose personal data is stored in clear.
+		pub fn student_age(student_id: u32) -> Option<u32> {
+			let personal = Students::<T>::get(student_id)?.personal?;
+			personal.date_of_birth.years_until(&Self::today())
+		}
+
+		/// Up to `limit` students of `owner`, continuing after `start_after` from a previous page.
+		/// The order is stable but arbitrary.
+		pub fn students_of(owner: T::AccountId, start_after: Option<u32>, limit: u32) -> Vec<u32> {
+			let limit = limit.min(T::MaxStudentsPerOwner::get()) as usize;
+			match start_after {
+				Some(last) => StudentsByOwner::<T>::iter_key_prefix_from(
+					&owner,
+					StudentsByOwner::<T>::hashed_key_for(&owner, last),
+				)
+				.take(limit)
+				.collect(),
+				None => StudentsByOwner::<T>::iter_key_prefix(&owner).take(limit).collect(),
+			}
+		}
+
+		/// Recorded changes of a student, oldest first, including those made on earlier chains.
+		pub fn student_timeline(student_id: u32) -> Vec<HistoryEntry<T::Hash>> {
+			StudentHistory::<T>::get(student_id).into_inner()
+		}
+
+		/// Alumni record of a student that graduated to another chain.
+		pub fn alumnus(student_id: u32) -> Option<AlumniRecordOf<T>> {
+			Alumni::<T>::get(student_id)
+		}
+
+		/// Open job offers of a student.
+		pub fn job_offers(student_id: u32) -> Vec<JobOffer<T>> {
+			JobOffers::<T>::get(student_id).into_inner()
+		}
+
+		/// Unpaid tuition invoices of a student.
+		pub fn unpaid_invoices(student_id: u32) -> Vec<Invoice<T>> {
+			Invoices::<T>::get(student_id).into_inner()
+		}
+
+		/// Graduation of a student waiting for the approval of its committee.
+		pub fn pending_graduation(student_id: u32) -> Option<PendingGraduation<T>> {
+			PendingGraduations::<T>::get(student_id)
+		}
+
+		/// Latest answer of the source chain about a received student.
+		pub fn verification(student_id: u32) -> Option<Verification<BlockNumberFor<T>>> {
+			Verifications::<T>::get(student_id)
+		}
+
+		/// Block in which the diploma of a student was revoked, if it was.
+		pub fn diploma_revoked_at(student_id: u32) -> Option<BlockNumberFor<T>> {
+			RevokedDiplomas::<T>::get(student_id)
+		}
+
+		/// Current job of a graduate, if employed through [`Pallet::hire_student`].
+		pub fn employment(student_id: u32) -> Option<Employment<T>> {
+			Employments::<T>::get(student_id)
+		}
+	}
+
+	
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		// CREATE STUDENT
+		#[pallet::call_index(0)]
+		#[pallet::weight(10_000)]
+		pub fn create_student(
+			origin: OriginFor<T>,
+			institution_id: u32,
+			data: PersonalDataInput<T::Hash>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			// Only admins of an accredited institution can enrol students in it
+			Self::ensure_accredited_admin(&who, institution_id)?;
+
+			// Validate and bound the personal data
+			let personal = Self::validate_personal_data(data)?;
+
+			// Generate new student ID
+			let student_id = Self::next_student_id()?;
+
+			// Build student struct
+			let student = Student::<T> {
+				personal: Some(personal),
+				status: StudentStatus::Enrolled,
+				status_since: Self::now(),
+				commitment: None,
+			};
+			Self::index_student(student_id, &student)?;
+			Self::record_change(student_id, Change::Created, None);
+
+			// Mint the item of the student in the collection of the institution
+			T::Items::mint(institution_id, student_id, &who, &student.encode())?;
+
+			// Insert into Students map
+			Students::<T>::insert(student_id, student);
+			StudentInstitution::<T>::insert(student_id, institution_id);
+
+			// Add student's ID into student's owner list
+			Self::add_owned(&who, student_id)?;
+
+			Ok(())
+		}
+
+
+		// GRADUATE STUDENT
+		#[pallet::call_index(1)]
+		#[pallet::weight(10_000)]
+		pub fn graduate_student(
+			origin: OriginFor<T>,
+			student_id: u32,
+			beneficiary: Option<Beneficiary>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			// Ensure the caller owns this student
+			Self::ensure_controls(&who, student_id)?;
+
+			// Get the student and ensure they exist
+			let student = Students::<T>::get(student_id).ok_or(Error::<T>::StudentNotFound)?;
+
+			// Ensure the student can graduate
+			Self::ensure_can_graduate(student_id, &student)?;
+
+			// Make sure the destination will be able to map the beneficiary to an account
+			let beneficiary = Self::beneficiary_location(beneficiary)?;
+
+			// A student with a linked account has to approve the transfer first
+			if let Some(student_account) = StudentAccounts::<T>::get(student_id) {
+				let now = frame_system::Pallet::<T>::block_number();
+				if let Some(pending) = PendingTransfers::<T>::get(student_id) {
+					ensure!(pending.expires_at < now, Error::<T>::TransferAlreadyPending);
+				}
+
+				let expires_at = now.saturating_add(T::TransferApprovalPeriod::get());
+				PendingTransfers::<T>::insert(
+					student_id,
+					PendingTransfer { requested_by: who, beneficiary, expires_at },
+				);
+
+				Self::deposit_event(Event::TransferRequested {
+					student_id,
+					student_account,
+					expires_at,
+				});
+
+				return Ok(())
+			}
+
+			let destination = Self::graduation_destination(student_id);
+			Self::graduate_once_approved(who, student_id, student, beneficiary, destination)
+		}
+
+
+		// DELETE STUDENT
+		#[pallet::call_index(2)]
+		#[pallet::weight(10_000)]
+		pub fn delete_student(
+			origin: OriginFor<T>,
+			student_id: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			// Ensure the student exists
+			let exists = Students::<T>::contains_key(student_id);
+			ensure!(exists, Error::<T>::StudentNotFound);
+
+			// Ensure the caller owns this student
+			Self::ensure_controls(&who, student_id)?;
+			Self::remove_owned(&who, student_id)?;
+
+			// Keep the record around for the grace period
+			let purge_at = Self::soft_delete(student_id, Some(who.clone()))?;
+
+			// Emit event
+			Self::deposit_event(Event::StudentDeleted { who, student_id, purge_at });
+
+			Ok(())
+		}
+
+
+		// RECEIVE STUDENT (is not called by user)
+		#[pallet::call_index(5)]
+		#[pallet::weight(10_000)]
+		pub fn receive_student(
+			origin: OriginFor<T>,
+			student: Student<T>,
+			source_id: u32,
+			beneficiary: Option<Location>,
+			history: HistoryOf<T>,
+			institution: u32,
+		) -> DispatchResult {
+			// Only a sibling parachain can send students. The account the source chain
+			// descended into only identifies the sender.
+			let location = T::XcmOrigin::ensure_origin(origin)?;
+			let para_id = Self::sibling_para_id(&location)?;
+			let sender = ExpectedStudents::<T>::take(para_id, source_id)
+				.ok_or(Error::<T>::StudentNotAnnounced)?;
+			ensure!(
+				Location::new(1, [Parachain(para_id)])
+					.appended_with(sender)
+					.is_ok_and(|expected| expected == location),
+				Error::<T>::StudentNotAnnounced
+			);
+			ensure!(Institutions::<T>::contains_key(institution), Error::<T>::InstitutionNotFound);
+
+			// The owner is the account derived from the XCM origin (source para + signer),
+			// unless the signer picked a beneficiary on this chain
+			let new_owner = match beneficiary {
+				Some(beneficiary) => T::LocationToAccountId::convert_location(&beneficiary)
+					.ok_or(Error::<T>::BeneficiaryNotConvertible)?,
+				None => T::LocationToAccountId::convert_location(&location)
+					.ok_or(Error::<T>::AccountNotConvertible)?,
+			};
+
+			ensure!(
+				!ReceivedIndex::<T>::contains_key(para_id, source_id),
+				Error::<T>::StudentAlreadyExists
+			);
+			let student_id = Self::next_student_id()?;
+			Self::index_student(student_id, &student)?;
+
+			// Continue the history kept on the sending chain
+			StudentHistory::<T>::insert(student_id, history);
+			Self::record_change(
+				student_id,
+				Change::Received { source_para: para_id, source_id },
+				None,
+			);
+
+			// Keep the hash of the record as sent, to have the source chain verify it later
+			ReceivedDiplomas::<T>::insert(student_id, Self::diploma_hash(&student));
+			Students::<T>::insert(student_id, student);
+			ReceivedFrom::<T>::insert(student_id, StudentOrigin { para_id, student_id: source_id });
+			ReceivedIndex::<T>::insert(para_id, source_id, student_id);
+			StudentInstitution::<T>::insert(student_id, institution);
+
+			// Add to new owner's list
+			Self::add_owned(&new_owner, student_id)?;
+
+			Self::deposit_event(Event::StudentReceived { student_id, owner: new_owner });
+
+			Ok(())
+		}
+
+
+		// UPDATE STUDENT
+		#[pallet::call_index(4)]
+		#[pallet::weight(10_000)]
+		pub fn update_student(
+			origin: OriginFor<T>,
+			student_id: u32,
+			data: PersonalDataInput<T::Hash>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			// Ensure the caller owns this student
+			Self::ensure_controls(&who, student_id)?;
+
+			// Get the student and ensure they exist
+			let mut student = Students::<T>::get(student_id)
+				.ok_or(Error::<T>::StudentNotFound)?;
+
+			// Replace the personal data with the validated input
+			Self::unindex_student(student_id, &student);
+			Self::record_change(student_id, Change::Updated, Some(&student));
+			let new = Self::validate_personal_data(data)?;
+			let old = student.personal.replace(new.clone());
+			Self::index_student(student_id, &student)?;
+
+			// Fields changed here win over later updates from the source chain
+			if let Some(old) = old.as_ref().filter(|_| ReceivedFrom::<T>::contains_key(student_id))
+			{
+				LocalEdits::<T>::mutate(student_id, |edits| {
+					*edits = edits.union(PersonalDataFields::changed(old, &new))
+				});
+			}
+
+			// Save updated student
+			Self::refresh_item(student_id, &student)?;
+			Students::<T>::insert(student_id, student);
+
+			// Emit event
+			Self::deposit_event(Event::StudentUpdated { who, student_id, old, new });
+
+			Ok(())
+		}
+
+
+		// DELETE ANY STUDENT (registrar, or an admin of the student's institution)
+		#[pallet::call_index(6)]
+		#[pallet::weight(10_000)]
+		pub fn delete_any_student(
+			origin: OriginFor<T>,
+			student_id: u32,
+		) -> DispatchResult {
+			let who = Self::ensure_signed_or_registrar(origin)?;
+
+			// Ensure the student exists
+			ensure!(Students::<T>::contains_key(student_id), Error::<T>::StudentNotFound);
+			if let Some(who) = &who {
+				ensure!(Self::administers(who, student_id), Error::<T>::NotInstitutionAdmin);
+			}
+
+			// Remove from owner's list if they have an owner
+			let owner = StudentOwner::<T>::get(student_id);
+			if let Some(owner) = &owner {
+				Self::remove_owned(owner, student_id)?;
+			}
+
+			// Keep the record around for the grace period
+			let purge_at = Self::soft_delete(student_id, owner)?;
+
+			Self::deposit_event(Event::StudentDeletedByAdmin { student_id, purge_at });
+
+			Ok(())
+		}
+
+		// CREATE STUDENT WITH COMMITTED PERSONAL DATA
+		// Only the salted hash of the personal data and an off-chain reference are stored
+		#[pallet::call_index(7)]
+		#[pallet::weight(10_000)]
+		pub fn create_committed_student(
+			origin: OriginFor<T>,
+			institution_id: u32,
+			commitment: T::Hash,
+			reference: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_accredited_admin(&who, institution_id)?;
+
+			let reference: BoundedVec<_, T::MaxReferenceLen> =
+				reference.try_into().map_err(|_| Error::<T>::ReferenceTooLong)?;
+
+			// Generate new student ID
+			let student_id = Self::next_student_id()?;
+
+			let student = Student::<T> {
+				commitment: Some(PiiCommitment { hash: commitment, reference }),
+				status_since: Self::now(),
+				..Default::default()
+			};
+			Self::index_student(student_id, &student)?;
+			Self::record_change(student_id, Change::Created, None);
+
+			T::Items::mint(institution_id, student_id, &who, &student.encode())?;
+			Students::<T>::insert(student_id, student);
+			StudentInstitution::<T>::insert(student_id, institution_id);
+
+			Self::add_owned(&who, student_id)?;
+
+			Self::deposit_event(Event::StudentCreated { who });
+
+			Ok(())
+		}
+
+		// VERIFY DISCLOSED PERSONAL DATA AGAINST THE COMMITMENT
+		#[pallet::call_index(8)]
+		#[pallet::weight(10_000)]
+		pub fn verify_student_data(
+			origin: OriginFor<T>,
+			student_id: u32,
+			data: PersonalDataInput<T::Hash>,
+			salt: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let student = Students::<T>::get(student_id).ok_or(Error::<T>::StudentNotFound)?;
+			let commitment = student.commitment.ok_or(Error::<T>::NoCommitment)?;
+			let personal = Self::validate_personal_data(data)?;
+
+			ensure!(
+				commitment.hash == Self::commitment_of(&personal, &salt),
+				Error::<T>::CommitmentMismatch
+			);
+
+			Self::deposit_event(Event::StudentDataVerified { who, student_id });
+
+			Ok(())
+		}
+
+		// ERASE PERSONAL DATA
+		// Wipes the plaintext fields here and on the chain the student was graduated to
+		#[pallet::call_index(9)]
+		#[pallet::weight(10_000)]
+		pub fn erase_student_data(origin: OriginFor<T>, student_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			if let Some(alumnus) = Alumni::<T>::get(student_id) {
+				// Only the copy on the destination is left
+				ensure!(alumnus.graduated_by == who, Error::<T>::NotStudentOwner);
+				// The reserved item still names the record by its hash
+				if let Some(institution_id) = alumnus.institution_id {
+					if T::Items::owner(institution_id, student_id).is_some() {
+						T::Items::set_metadata(institution_id, student_id, &[])?;
+					}
+				}
+				let destination = Self::send_call(
+					alumnus.destination_para,
+					None,
+					Call::<T>::receive_erasure { source_id: student_id },
+				)?;
+				Self::deposit_event(Event::XcmMessageSent { destination });
+				return Ok(())
+			}
+
+			// Ensure the caller owns this student
+			Self::ensure_controls(&who, student_id)?;
+
+			Self::erase_record(student_id)
+		}
+
+		// RECEIVE ERASURE (is not called by user)
+		// `source_id` is the id of the student on the sending parachain
+		#[pallet::call_index(10)]
+		#[pallet::weight(10_000)]
+		pub fn receive_erasure(origin: OriginFor<T>, source_id: u32) -> DispatchResult {
+			let para_id = Self::ensure_sibling_chain(origin)?;
+
+			let student_id =
+				ReceivedIndex::<T>::get(para_id, source_id).ok_or(Error::<T>::StudentNotFound)?;
+
+			Self::erase_record(student_id)
+		}
+
+		// LINK STUDENT ACCOUNT
+		// Once linked, the student has to approve every graduation transfer, and only the
+		// student can change or unlink the account
+		#[pallet::call_index(11)]
+		#[pallet::weight(10_000)]
+		pub fn set_student_account(
+			origin: OriginFor<T>,
+			student_id: u32,
+			account: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			match StudentAccounts::<T>::get(student_id) {
+				Some(linked) => ensure!(linked == who, Error::<T>::NotStudentAccount),
+				None => Self::ensure_controls(&who, student_id)?,
+			}
+
+			// A pending request was made with the previous account in mind
+			PendingTransfers::<T>::remove(student_id);
+			StudentAccounts::<T>::set(student_id, account.clone());
+
+			Self::deposit_event(Event::StudentAccountSet { student_id, account });
+
+			Ok(())
+		}
+
+		// APPROVE TRANSFER (signed by the student)
+		#[pallet::call_index(12)]
+		#[pallet::weight(10_000)]
+		pub fn approve_transfer(origin: OriginFor<T>, student_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				StudentAccounts::<T>::get(student_id).as_ref() == Some(&who),
+				Error::<T>::NotStudentAccount
+			);
+
+			let pending =
+				PendingTransfers::<T>::take(student_id).ok_or(Error::<T>::NoPendingTransfer)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= pending.expires_at,
+				Error::<T>::TransferExpired
+			);
+
+			// The requester must still own the student
+			Self::ensure_controls(&pending.requested_by, student_id)?;
+
+			let student = Students::<T>::get(student_id).ok_or(Error::<T>::StudentNotFound)?;
+			Self::ensure_can_graduate(student_id, &student)?;
+
+			Self::deposit_event(Event::TransferApproved { student_id, who });
+
+			let destination = Self::graduation_destination(student_id);
+			Self::graduate_once_approved(
+				pending.requested_by,
+				student_id,
+				student,
+				pending.beneficiary,
+				destination,
+			)
+		}
+
+		// SUSPEND STUDENT
+		#[pallet::call_index(13)]
+		#[pallet::weight(10_000)]
+		pub fn suspend_student(origin: OriginFor<T>, student_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::change_status(&who, student_id, StudentStatus::Suspended)
+		}
+
+		// GRANT LEAVE OF ABSENCE
+		#[pallet::call_index(14)]
+		#[pallet::weight(10_000)]
+		pub fn grant_leave(origin: OriginFor<T>, student_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::change_status(&who, student_id, StudentStatus::OnLeave)
+		}
+
+		// REINSTATE A SUSPENDED, ON LEAVE OR WITHDRAWN STUDENT
+		#[pallet::call_index(15)]
+		#[pallet::weight(10_000)]
+		pub fn reinstate_student(origin: OriginFor<T>, student_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::change_status(&who, student_id, StudentStatus::Enrolled)
+		}
+
+		// WITHDRAW STUDENT
+		#[pallet::call_index(16)]
+		#[pallet::weight(10_000)]
+		pub fn withdraw_student(origin: OriginFor<T>, student_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::change_status(&who, student_id, StudentStatus::Withdrawn)
+		}
+
+		// MARK A GRADUATE AS EMPLOYED OR NO LONGER EMPLOYED
+		#[pallet::call_index(17)]
+		#[pallet::weight(10_000)]
+		pub fn set_employed(
+			origin: OriginFor<T>,
+			student_id: u32,
+			employed: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Employments::<T>::contains_key(student_id), Error::<T>::HasEmployment);
+			let status = if employed { StudentStatus::Employed } else { StudentStatus::Graduated };
+			Self::change_status(&who, student_id, status)
+		}
+
+		// RESTORE A DELETED STUDENT
+		// Possible for the former owner or the registrar until the grace period is over
+		#[pallet::call_index(18)]
+		#[pallet::weight(10_000)]
+		pub fn restore_student(origin: OriginFor<T>, student_id: u32) -> DispatchResult {
+			let who = Self::ensure_signed_or_registrar(origin)?;
+
+			let deleted =
+				DeletedStudents::<T>::get(student_id).ok_or(Error::<T>::StudentNotDeleted)?;
+			if let Some(who) = &who {
+				ensure!(
+					deleted.owner.as_ref() == Some(who) || Self::administers(who, student_id),
+					Error::<T>::NotStudentOwner
+				);
+			}
+			ensure!(
+				frame_system::Pallet::<T>::block_number() < deleted.purge_at,
+				Error::<T>::RestoreWindowExpired
+			);
+
+			DeletedStudents::<T>::remove(student_id);
+			PurgeAgenda::<T>::mutate(deleted.purge_at, |ids| ids.retain(|id| *id != student_id));
+
+			if let Some(owner) = &deleted.owner {
+				Self::add_owned(owner, student_id)?;
+			}
+			Self::record_change(student_id, Change::Restored, None);
+			Students::<T>::insert(student_id, deleted.student);
+
+			Self::deposit_event(Event::StudentRestored { student_id });
+
+			Ok(())
+		}
+
+		// REGISTER AN INSTITUTION, PENDING ACCREDITATION
+		#[pallet::call_index(19)]
+		#[pallet::weight(10_000)]
+		pub fn register_institution(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			admins: Vec<T::AccountId>,
+		) -> DispatchResult {
+			T::InstitutionRegistrar::ensure_origin(origin)?;
+
+			let name: BoundedVec<_, T::MaxInstitutionNameLen> =
+				name.try_into().map_err(|_| Error::<T>::InstitutionNameTooLong)?;
+			let admins: BoundedVec<_, T::MaxInstitutionAdmins> =
+				admins.try_into().map_err(|_| Error::<T>::TooManyInstitutionAdmins)?;
+
+			let institution_id = InstitutionCount::<T>::get();
+			let next = institution_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			Institutions::<T>::insert(
+				institution_id,
+				Institution::<T> {
+					name,
+					admins,
+					accreditation: Accreditation::Pending,
+					graduation_destination: None,
+				},
+			);
+			T::Items::create_collection(institution_id)?;
+			InstitutionCount::<T>::put(next);
+
+			Self::deposit_event(Event::InstitutionRegistered { institution_id });
+
+			Ok(())
+		}
+
+		// SET THE ACCREDITATION OF AN INSTITUTION
+		#[pallet::call_index(20)]
+		#[pallet::weight(10_000)]
+		pub fn set_accreditation(
+			origin: OriginFor<T>,
+			institution_id: u32,
+			accreditation: Accreditation,
+		) -> DispatchResult {
+			T::InstitutionRegistrar::ensure_origin(origin)?;
+
+			Institutions::<T>::try_mutate(institution_id, |institution| {
+				let institution = institution.as_mut().ok_or(Error::<T>::InstitutionNotFound)?;
+				institution.accreditation = accreditation;
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::AccreditationChanged { institution_id, accreditation });
+
+			Ok(())
+		}
+
+		// REPLACE THE ADMINS OF AN INSTITUTION (registrar only)
+		#[pallet::call_index(21)]
+		#[pallet::weight(10_000)]
+		pub fn set_institution_admins(
+			origin: OriginFor<T>,
+			institution_id: u32,
+			admins: Vec<T::AccountId>,
+		) -> DispatchResult {
+			T::InstitutionRegistrar::ensure_origin(origin)?;
+
+			let admins: BoundedVec<_, T::MaxInstitutionAdmins> =
+				admins.try_into().map_err(|_| Error::<T>::TooManyInstitutionAdmins)?;
+			Institutions::<T>::try_mutate(institution_id, |institution| {
+				let institution = institution.as_mut().ok_or(Error::<T>::InstitutionNotFound)?;
+				institution.admins = admins;
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::InstitutionAdminsSet { institution_id });
+
+			Ok(())
+		}
+
+		// SET WHERE AN INSTITUTION'S GRADUATES ARE SENT (admins only)
+		#[pallet::call_index(22)]
+		#[pallet::weight(10_000)]
+		pub fn set_graduation_destination(
+			origin: OriginFor<T>,
+			institution_id: u32,
+			destination: Option<GraduationDestination>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut institution = Self::ensure_institution_admin(&who, institution_id)?;
+			institution.graduation_destination = destination.clone();
+			Institutions::<T>::insert(institution_id, institution);
+
+			Self::deposit_event(Event::GraduationDestinationSet { institution_id, destination });
+
+			Ok(())
+		}
+
+		// HIRE A GRADUATE RECEIVED FROM ANOTHER CHAIN (admins of the employer only)
+		#[pallet::call_index(23)]
+		#[pallet::weight(10_000)]
+		pub fn hire_student(
+			origin: OriginFor<T>,
+			student_id: u32,
+			employer: u32,
+			title: Vec<u8>,
+			department: Vec<u8>,
+			contract: ContractType,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_accredited_admin(&who, employer)?;
+
+			// Only graduates sent here by another chain can be hired
+			ensure!(ReceivedFrom::<T>::contains_key(student_id), Error::<T>::NotReceivedStudent);
+			ensure!(!RevokedDiplomas::<T>::contains_key(student_id), Error::<T>::DiplomaRevoked);
+			let position = Self::position_from(title, department, contract)?;
+
+			Students::<T>::try_mutate(student_id, |student| {
+				let student = student.as_mut().ok_or(Error::<T>::StudentNotFound)?;
+				Self::transition(student_id, student, StudentStatus::Employed)
+			})?;
+			Employments::<T>::insert(
+				student_id,
+				Employment {
+					employer,
+					position: position.clone(),
+					start: frame_system::Pallet::<T>::block_number(),
+					promoted_at: None,
+				},
+			);
+
+			Self::deposit_event(Event::StudentHired { student_id, employer, position });
+
+			Ok(())
+		}
+
+		// MOVE AN EMPLOYEE TO ANOTHER POSITION
+		#[pallet::call_index(24)]
+		#[pallet::weight(10_000)]
+		pub fn promote_employee(
+			origin: OriginFor<T>,
+			student_id: u32,
+			title: Vec<u8>,
+			department: Vec<u8>,
+			contract: ContractType,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut employment =
+				Employments::<T>::get(student_id).ok_or(Error::<T>::NotEmployed)?;
+			Self::ensure_institution_admin(&who, employment.employer)?;
+
+			let position = Self::position_from(title, department, contract)?;
+			Self::record_change(
+				student_id,
+				Change::Promoted,
+				Students::<T>::get(student_id).as_ref(),
+			);
+			employment.position = position.clone();
+			employment.promoted_at = Some(frame_system::Pallet::<T>::block_number());
+			Employments::<T>::insert(student_id, employment);
+
+			Self::deposit_event(Event::StudentPromoted { student_id, position });
+
+			Ok(())
+		}
+
+		// END AN EMPLOYMENT, THE STUDENT GOES BACK TO GRADUATED
+		#[pallet::call_index(25)]
+		#[pallet::weight(10_000)]
+		pub fn terminate_employment(origin: OriginFor<T>, student_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let employment = Employments::<T>::get(student_id).ok_or(Error::<T>::NotEmployed)?;
+			Self::ensure_institution_admin(&who, employment.employer)?;
+
+			Students::<T>::try_mutate(student_id, |student| {
+				let student = student.as_mut().ok_or(Error::<T>::StudentNotFound)?;
+				Self::transition(student_id, student, StudentStatus::Graduated)
+			})?;
+			Employments::<T>::remove(student_id);
+
+			Self::deposit_event(Event::EmploymentTerminated {
+				student_id,
+				employer: employment.employer,
+			});
+
+			Ok(())
+		}
+
+		// OFFER A JOB TO A STUDENT OF A SIBLING CHAIN (admins of the employer only)
+		#[pallet::call_index(26)]
+		#[pallet::weight(10_000)]
+		pub fn send_job_offer(
+			origin: OriginFor<T>,
+			employer: u32,
+			para_id: u32,
+			student_id: u32,
+			title: Vec<u8>,
+			department: Vec<u8>,
+			contract: ContractType,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_accredited_admin(&who, employer)?;
+
+			let position = Self::position_from(title, department, contract)?;
+			let destination = Self::send_call(
+				para_id,
+				None,
+				Call::<T>::receive_job_offer { student_id, employer, position },
+			)?;
+
+			Self::deposit_event(Event::JobOfferSent { employer, destination, student_id });
+
+			Ok(())
+		}
+
+		// RECEIVE A JOB OFFER FROM A SIBLING CHAIN
+		// A new offer from the same employer replaces the previous one
+		#[pallet::call_index(27)]
+		#[pallet::weight(10_000)]
+		pub fn receive_job_offer(
+			origin: OriginFor<T>,
+			student_id: u32,
+			employer: u32,
+			position: Position<T>,
+		) -> DispatchResult {
+			let para_id = Self::ensure_sibling_chain(origin)?;
+
+			let student = Students::<T>::get(student_id).ok_or(Error::<T>::StudentNotFound)?;
+			ensure!(!student.status.has_graduated(), Error::<T>::AlreadyGraduated);
+
+			JobOffers::<T>::try_mutate(student_id, |offers| {
+				offers.retain(|offer| offer.para_id != para_id || offer.employer != employer);
+				let from_para = offers.iter().filter(|offer| offer.para_id == para_id).count();
+				ensure!(
+					(from_para as u32) < T::MaxJobOffersPerPara::get(),
+					Error::<T>::TooManyJobOffersFromPara
+				);
+				offers
+					.try_push(JobOffer {
+						para_id,
+						employer,
+						position,
+						received_at: frame_system::Pallet::<T>::block_number(),
+					})
+					.map_err(|_| Error::<T>::TooManyJobOffers)
+			})?;
+
+			Self::deposit_event(Event::JobOfferReceived { student_id, para_id, employer });
+
+			Ok(())
+		}
+
+		// ACCEPT A JOB OFFER, GRADUATING TO THE EMPLOYER'S CHAIN (the student only)
+		#[pallet::call_index(28)]
+		#[pallet::weight(10_000)]
+		pub fn accept_job_offer(
+			origin: OriginFor<T>,
+			student_id: u32,
+			para_id: u32,
+			employer: u32,
+			beneficiary: Option<Beneficiary>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::take_job_offer(&who, student_id, para_id, employer)?;
+
+			let owner = StudentOwner::<T>::get(student_id).ok_or(Error::<T>::StudentNotFound)?;
+			let student = Students::<T>::get(student_id).ok_or(Error::<T>::StudentNotFound)?;
+			Self::ensure_can_graduate(student_id, &student)?;
+			let beneficiary = Self::beneficiary_location(beneficiary)?;
+
+			Self::deposit_event(Event::JobOfferAccepted { student_id, para_id, employer });
+
+			// Accepting is the student's approval, no transfer request is needed
+			Self::graduate_once_approved(
+				owner,
+				student_id,
+				student,
+				beneficiary,
+				GraduationDestination { para_id, institution: employer },
+			)
+		}
+
+		// DECLINE A JOB OFFER (the student only)
+		#[pallet::call_index(29)]
+		#[pallet::weight(10_000)]
+		pub fn decline_job_offer(
+			origin: OriginFor<T>,
+			student_id: u32,
+			para_id: u32,
+			employer: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::take_job_offer(&who, student_id, para_id, employer)?;
+
+			Self::deposit_event(Event::JobOfferDeclined { student_id, para_id, employer });
+
+			Ok(())
+		}
+
+		// ASK THE SOURCE CHAIN WHETHER THE DIPLOMA OF A RECEIVED STUDENT IS STILL VALID
+		// The answer arrives as a query response, see `verification_response`
+		#[pallet::call_index(30)]
+		#[pallet::weight(10_000)]
+		pub fn request_verification(origin: OriginFor<T>, student_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Self::owns(&who, student_id) || Self::administers(&who, student_id),
+				Error::<T>::NotStudentOwner
+			);
+
+			let source =
+				ReceivedFrom::<T>::get(student_id).ok_or(Error::<T>::NotReceivedStudent)?;
+			let diploma_hash =
+				ReceivedDiplomas::<T>::get(student_id).ok_or(Error::<T>::NotReceivedStudent)?;
+			PendingVerificationCount::<T>::try_mutate(student_id, |count| {
+				ensure!(
+					*count < T::MaxPendingVerifications::get(),
+					Error::<T>::TooManyPendingVerifications
+				);
+				*count += 1;
+				Ok::<_, DispatchError>(())
+			})?;
+
+			// Register the query, the source chain answers with the outcome of `verify_diploma`
+			let responder = Location::new(1, [Parachain(source.para_id)]);
+			let destination = T::UniversalLocation::get()
+				.invert_target(&responder)
+				.map_err(|_| Error::<T>::XcmSendFailed)?;
+			let timeout = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::VerificationTimeout::get());
+			let notify = Call::<T>::verification_response { query_id: 0, response: Response::Null };
+			let query_id = T::Queries::new_notify_query(responder, notify.into(), timeout);
+			PendingVerifications::<T>::insert(query_id, student_id);
+			// A late answer never arrives, so the request is dropped once it times out
+			VerificationAgenda::<T>::try_mutate(timeout.saturating_add(One::one()), |ids| {
+				ids.try_push(query_id)
+			})
+			.map_err(|_| Error::<T>::TooManyVerifications)?;
+
+			Self::send_transact(
+				source.para_id,
+				None,
+				None,
+				Call::<T>::verify_diploma { student_id: source.student_id, diploma_hash },
+				Some(QueryResponseInfo {
+					destination,
+					query_id,
+					max_weight: Weight::from_parts(1_000_000_000, 64 * 1024),
+				}),
+				None,
+			)?;
+
+			Self::deposit_event(Event::VerificationRequested { student_id, query_id });
+
+			Ok(())
+		}
+
+		// CHECK A DIPLOMA ON BEHALF OF THE CHAIN THE STUDENT WAS SENT TO
+		// Fails unless the student graduated to the calling chain with this exact record
+		#[pallet::call_index(31)]
+		#[pallet::weight(10_000)]
+		pub fn verify_diploma(
+			origin: OriginFor<T>,
+			student_id: u32,
+			diploma_hash: T::Hash,
+		) -> DispatchResult {
+			let para_id = Self::ensure_sibling_chain(origin)?;
+
+			let alumnus = Alumni::<T>::get(student_id).ok_or(Error::<T>::NotAlumnus)?;
+			ensure!(alumnus.destination_para == para_id, Error::<T>::NotAlumnus);
+			ensure!(alumnus.diploma_hash == diploma_hash, Error::<T>::DiplomaMismatch);
+			ensure!(!RevokedDiplomas::<T>::contains_key(student_id), Error::<T>::DiplomaRevoked);
+
+			Ok(())
+		}
+
+		// ANSWER OF THE SOURCE CHAIN TO `request_verification`
+		#[pallet::call_index(32)]
+		#[pallet::weight(10_000)]
+		pub fn verification_response(
+			origin: OriginFor<T>,
+			query_id: QueryId,
+			response: Response,
+		) -> DispatchResult {
+			T::ResponseOrigin::ensure_origin(origin)?;
+
+			let student_id = Self::settle_verification(query_id).ok_or(Error::<T>::UnknownQuery)?;
+			// The student may have left in the meantime
+			if !Students::<T>::contains_key(student_id) {
+				return Ok(())
+			}
+
+			let valid = matches!(response, Response::DispatchResult(MaybeErrorCode::Success));
+			Verifications::<T>::insert(
+				student_id,
+				Verification { valid, at: frame_system::Pallet::<T>::block_number() },
+			);
+
+			Self::deposit_event(Event::StudentVerified { student_id, valid });
+
+			Ok(())
+		}
+
+		// REVOKE THE DIPLOMA OF A STUDENT THAT GRADUATED TO ANOTHER CHAIN
+		// Possible for the registrar or the owner that graduated the student; the copy on the
+		// destination is marked revoked too
+		#[pallet::call_index(33)]
+		#[pallet::weight(10_000)]
+		pub fn revoke_diploma(origin: OriginFor<T>, student_id: u32) -> DispatchResult {
+			let who = Self::ensure_signed_or_registrar(origin)?;
+
+			let alumnus = Alumni::<T>::get(student_id).ok_or(Error::<T>::NotAlumnus)?;
+			if let Some(who) = &who {
+				ensure!(alumnus.graduated_by == *who, Error::<T>::NotStudentOwner);
+			}
+			ensure!(!RevokedDiplomas::<T>::contains_key(student_id), Error::<T>::DiplomaRevoked);
+
+			RevokedDiplomas::<T>::insert(student_id, frame_system::Pallet::<T>::block_number());
+			let destination = Self::send_call(
+				alumnus.destination_para,
+				None,
+				Call::<T>::receive_revocation { source_id: student_id },
+			)?;
+
+			Self::deposit_event(Event::DiplomaRevoked { student_id, destination });
+
+			Ok(())
+		}
+
+		// RECEIVE REVOCATION (is not called by user)
+		// `source_id` is the id of the student on the sending parachain
+		#[pallet::call_index(34)]
+		#[pallet::weight(10_000)]
+		pub fn receive_revocation(origin: OriginFor<T>, source_id: u32) -> DispatchResult {
+			let source_para = Self::ensure_sibling_chain(origin)?;
+
+			let student_id = ReceivedIndex::<T>::get(source_para, source_id)
+				.ok_or(Error::<T>::StudentNotFound)?;
+
+			Self::record_change(
+				student_id,
+				Change::Revoked,
+				Students::<T>::get(student_id).as_ref(),
+			);
+			RevokedDiplomas::<T>::insert(student_id, frame_system::Pallet::<T>::block_number());
+
+			Self::deposit_event(Event::ReceivedDiplomaRevoked { student_id, source_para });
+
+			Ok(())
+		}
+
+		// SUBSCRIBE TO UPDATES OF A RECEIVED STUDENT FROM ITS SOURCE CHAIN, OR UNSUBSCRIBE
+		#[pallet::call_index(35)]
+		#[pallet::weight(10_000)]
+		pub fn set_update_subscription(
+			origin: OriginFor<T>,
+			student_id: u32,
+			subscribed: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Self::owns(&who, student_id) || Self::administers(&who, student_id),
+				Error::<T>::NotStudentOwner
+			);
+
+			let source =
+				ReceivedFrom::<T>::get(student_id).ok_or(Error::<T>::NotReceivedStudent)?;
+			Self::send_call(
+				source.para_id,
+				None,
+				Call::<T>::receive_subscription { source_id: source.student_id, subscribed },
+			)?;
+
+			Self::deposit_event(Event::UpdateSubscriptionRequested { student_id, subscribed });
+
+			Ok(())
+		}
+
+		// RECEIVE A SUBSCRIPTION CHANGE FROM THE DESTINATION OF AN ALUMNUS (is not called by user)
+		#[pallet::call_index(36)]
+		#[pallet::weight(10_000)]
+		pub fn receive_subscription(
+			origin: OriginFor<T>,
+			source_id: u32,
+			subscribed: bool,
+		) -> DispatchResult {
+			let para_id = Self::ensure_sibling_chain(origin)?;
+
+			// Only the chain holding the record can subscribe to it
+			let alumnus = Alumni::<T>::get(source_id).ok_or(Error::<T>::NotAlumnus)?;
+			ensure!(alumnus.destination_para == para_id, Error::<T>::NotAlumnus);
+
+			if subscribed {
+				UpdateSubscriptions::<T>::insert(source_id, ());
+			} else {
+				UpdateSubscriptions::<T>::remove(source_id);
+			}
+
+			Self::deposit_event(Event::UpdateSubscriptionChanged {
+				student_id: source_id,
+				para_id,
+				subscribed,
+			});
+
+			Ok(())
+		}
+
+		// AMEND THE PERSONAL DATA OF AN ALUMNUS ON THE CHAIN HOLDING THE RECORD
+		// Possible for the registrar or the owner that graduated the student, once the
+		// destination subscribed to updates
+		#[pallet::call_index(37)]
+		#[pallet::weight(10_000)]
+		pub fn amend_alumnus(
+			origin: OriginFor<T>,
+			student_id: u32,
+			delta: PersonalDataDelta<T>,
+		) -> DispatchResult {
+			let who = Self::ensure_signed_or_registrar(origin)?;
+
+			let alumnus = Alumni::<T>::get(student_id).ok_or(Error::<T>::NotAlumnus)?;
+			if let Some(who) = &who {
+				ensure!(alumnus.graduated_by == *who, Error::<T>::NotStudentOwner);
+			}
+			ensure!(UpdateSubscriptions::<T>::contains_key(student_id), Error::<T>::NotSubscribed);
+
+			Self::validate_delta(&delta)?;
+
+			let destination = Self::send_call(
+				alumnus.destination_para,
+				None,
+				Call::<T>::receive_update { source_id: student_id, delta },
+			)?;
+
+			Self::deposit_event(Event::AlumnusAmended { student_id, destination });
+
+			Ok(())
+		}
+
+		// RECEIVE AN UPDATE FROM THE SOURCE CHAIN OF A STUDENT (is not called by user)
+		// Fields changed on this chain since the student arrived are kept
+		#[pallet::call_index(38)]
+		#[pallet::weight(10_000)]
+		pub fn receive_update(
+			origin: OriginFor<T>,
+			source_id: u32,
+			delta: PersonalDataDelta<T>,
+		) -> DispatchResult {
+			let para_id = Self::ensure_sibling_chain(origin)?;
+
+			let student_id =
+				ReceivedIndex::<T>::get(para_id, source_id).ok_or(Error::<T>::StudentNotFound)?;
+			let mut student = Students::<T>::get(student_id).ok_or(Error::<T>::StudentNotFound)?;
+			// Nothing to update once erased, or when only committed to
+			let Some(mut personal) = student.personal.clone() else { return Ok(()) };
+
+			let (applied, kept) = delta.apply_to(&mut personal, LocalEdits::<T>::get(student_id));
+			if !applied.is_empty() {
+				Self::unindex_student(student_id, &student);
+				Self::record_change(student_id, Change::Updated, Some(&student));
+				student.personal = Some(Self::validate_personal_data(personal.into())?);
+				Self::index_student(student_id, &student)?;
+				Self::refresh_item(student_id, &student)?;
+				Students::<T>::insert(student_id, student);
+			}
+
+			Self::deposit_event(Event::StudentUpdatedFromSource { student_id, applied, kept });
+
+			Ok(())
+		}
+
+		// TOP UP THE SCHOLARSHIP FUND (admins only)
+		#[pallet::call_index(39)]
+		#[pallet::weight(10_000)]
+		pub fn fund_scholarships(
+			origin: OriginFor<T>,
+			institution_id: u32,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_institution_admin(&who, institution_id)?;
+
+			let fund = Self::institution_account(institution_id);
+			T::Currency::transfer(&who, &fund, amount, Preservation::Preserve)?;
+
+			Self::deposit_event(Event::ScholarshipFundToppedUp { institution_id, who, amount });
+
+			Ok(())
+		}
+
+		// AWARD A STIPEND TO A STUDENT (admins of its institution only)
+		#[pallet::call_index(40)]
+		#[pallet::weight(10_000)]
+		pub fn award_stipend(
+			origin: OriginFor<T>,
+			student_id: u32,
+			amount: BalanceOf<T>,
+			after_graduation: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let student = Students::<T>::get(student_id).ok_or(Error::<T>::StudentNotFound)?;
+			let institution =
+				StudentInstitution::<T>::get(student_id).ok_or(Error::<T>::InstitutionNotFound)?;
+			Self::ensure_institution_admin(&who, institution)?;
+			ensure!(!student.status.has_graduated(), Error::<T>::AlreadyGraduated);
+			ensure!(!Stipends::<T>::contains_key(student_id), Error::<T>::StipendAlreadyAwarded);
+			ensure!(StudentAccounts::<T>::contains_key(student_id), Error::<T>::NoStudentAccount);
+
+			let first_payment =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::StipendPeriod::get());
+			StipendAgenda::<T>::try_mutate(first_payment, |ids| ids.try_push(student_id))
+				.map_err(|_| Error::<T>::TooManyStipends)?;
+			Stipends::<T>::insert(
+				student_id,
+				Stipend {
+					institution,
+					amount,
+					payee: StipendPayee::Student,
+					after_graduation,
+					next_payment: first_payment,
+				},
+			);
+
+			Self::deposit_event(Event::StipendAwarded { student_id, amount, first_payment });
+
+			Ok(())
+		}
+
+		// REVOKE THE STIPEND OF A STUDENT OR GRADUATE (admins of the awarding institution only)
+		#[pallet::call_index(41)]
+		#[pallet::weight(10_000)]
+		pub fn revoke_stipend(origin: OriginFor<T>, student_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let stipend = Stipends::<T>::get(student_id).ok_or(Error::<T>::NoStipend)?;
+			Self::ensure_institution_admin(&who, stipend.institution)?;
+			Self::remove_stipend(student_id, &stipend);
+
+			Self::deposit_event(Event::StipendRevoked { student_id });
+
+			Ok(())
+		}
+
+		// ISSUE A TUITION INVOICE TO A STUDENT (admins of its institution only)
+		#[pallet::call_index(42)]
+		#[pallet::weight(10_000)]
+		pub fn issue_invoice(
+			origin: OriginFor<T>,
+			student_id: u32,
+			amount: BalanceOf<T>,
+			due: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let student = Students::<T>::get(student_id).ok_or(Error::<T>::StudentNotFound)?;
+			let institution =
+				StudentInstitution::<T>::get(student_id).ok_or(Error::<T>::InstitutionNotFound)?;
+			Self::ensure_institution_admin(&who, institution)?;
+			ensure!(!student.status.has_graduated(), Error::<T>::AlreadyGraduated);
+
+			let invoice_id = InvoiceCount::<T>::get();
+			let next_id = invoice_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			Invoices::<T>::try_mutate(student_id, |invoices| {
+				invoices.try_push(Invoice { id: invoice_id, institution, amount, due })
+			})
+			.map_err(|_| Error::<T>::TooManyInvoices)?;
+			InvoiceCount::<T>::put(next_id);
+
+			Self::deposit_event(Event::InvoiceIssued { student_id, invoice_id, amount, due });
+
+			Ok(())
+		}
+
+		// PAY A TUITION INVOICE INTO THE INSTITUTION ACCOUNT (anyone, e.g. the owner or a sponsor)
+		#[pallet::call_index(43)]
+		#[pallet::weight(10_000)]
+		pub fn pay_invoice(
+			origin: OriginFor<T>,
+			student_id: u32,
+			invoice_id: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
+			let invoice = Self::take_invoice(student_id, invoice_id)?;
+			T::Currency::transfer(
+				&who,
+				&Self::institution_account(invoice.institution),
+				invoice.amount,
+				Preservation::Preserve,
+			)?;
 
-		// DELETE STUDENT
-		#[pallet::call_index(2)]
+			Self::deposit_event(Event::InvoicePaid { student_id, invoice_id, who });
+
+			Ok(())
+		}
+
+		// CANCEL A TUITION INVOICE (admins of the issuing institution only)
+		#[pallet::call_index(44)]
 		#[pallet::weight(10_000)]
-		pub fn delete_student(
+		pub fn cancel_invoice(
 			origin: OriginFor<T>,
 			student_id: u32,
+			invoice_id: u32,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			// Ensure the student exists
-			let exists = Students::<T>::contains_key(student_id);
-			ensure!(exists, Error::<T>::StudentNotFound);
+			let invoice = Self::take_invoice(student_id, invoice_id)?;
+			Self::ensure_institution_admin(&who, invoice.institution)?;
 
-			// Ensure the caller owns this student
-			StudentsByOwner::<T>::try_mutate(&who, |owned_ids| {
-				if let Some(index) = owned_ids.iter().position(|id| *id == student_id) {
-					owned_ids.swap_remove(index); // efficient removal
-					Ok(())
-				} else {
-					Err(Error::<T>::NotStudentOwner)
-				}
-			})?;
+			Self::deposit_event(Event::InvoiceCancelled { student_id, invoice_id });
+
+			Ok(())
+		}
 
-			// Remove the student record
-			Students::<T>::remove(student_id);
+		// SET THE GRADUATION COMMITTEE OF AN INSTITUTION (registrar only)
+		// Admins are the ones the committee checks, so they cannot change or remove it
+		#[pallet::call_index(45)]
+		#[pallet::weight(10_000)]
+		pub fn set_graduation_committee(
+			origin: OriginFor<T>,
+			institution_id: u32,
+			members: Vec<T::AccountId>,
+			threshold: u32,
+		) -> DispatchResult {
+			T::InstitutionRegistrar::ensure_origin(origin)?;
+			ensure!(
+				Institutions::<T>::contains_key(institution_id),
+				Error::<T>::InstitutionNotFound
+			);
+
+			// Without members, graduations no longer need approval
+			if members.is_empty() {
+				GraduationCommittees::<T>::remove(institution_id);
+				Self::deposit_event(Event::GraduationCommitteeSet {
+					institution_id,
+					threshold: None,
+				});
+				return Ok(())
+			}
 
-			// Emit event
-			Self::deposit_event(Event::StudentDeleted { who, student_id });
+			ensure!(
+				threshold > 0 && threshold as usize <= members.len(),
+				Error::<T>::InvalidThreshold
+			);
+			let mut sorted = members.clone();
+			sorted.sort();
+			sorted.dedup();
+			ensure!(sorted.len() == members.len(), Error::<T>::DuplicateCommitteeMember);
+			let members: BoundedVec<_, T::MaxCommitteeMembers> =
+				members.try_into().map_err(|_| Error::<T>::TooManyCommitteeMembers)?;
+			GraduationCommittees::<T>::insert(institution_id, Committee { members, threshold });
+
+			Self::deposit_event(Event::GraduationCommitteeSet {
+				institution_id,
+				threshold: Some(threshold),
+			});
 
 			Ok(())
 		}
 
+		// APPROVE A GRADUATION, SENDING THE STUDENT AT THE THRESHOLD (committee members only)
+		#[pallet::call_index(46)]
+		#[pallet::weight(10_000)]
+		pub fn approve_graduation(origin: OriginFor<T>, student_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut pending =
+				PendingGraduations::<T>::take(student_id).ok_or(Error::<T>::NoPendingGraduation)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= pending.expires_at,
+				Error::<T>::GraduationExpired
+			);
+			let institution =
+				StudentInstitution::<T>::get(student_id).ok_or(Error::<T>::InstitutionNotFound)?;
+			let committee = GraduationCommittees::<T>::get(institution)
+				.ok_or(Error::<T>::NotCommitteeMember)?;
+			ensure!(committee.members.contains(&who), Error::<T>::NotCommitteeMember);
+			ensure!(!pending.approvals.contains(&who), Error::<T>::AlreadyApproved);
+			pending
+				.approvals
+				.try_push(who.clone())
+				.map_err(|_| Error::<T>::TooManyCommitteeMembers)?;
+
+			// Members that left the committee no longer count
+			let approvals = pending
+				.approvals
+				.iter()
+				.filter(|member| committee.members.contains(member))
+				.count() as u32;
+			Self::deposit_event(Event::GraduationApproved { student_id, who, approvals });
+
+			if approvals < committee.threshold {
+				PendingGraduations::<T>::insert(student_id, pending);
+				return Ok(())
+			}
 
-		// RECEIVE STUDENT (is not called by user)
-		#[pallet::call_index(5)]
+			// The owner must still own the student, which must still be able to graduate
+			Self::ensure_controls(&pending.owner, student_id)?;
+			let student = Students::<T>::get(student_id).ok_or(Error::<T>::StudentNotFound)?;
+			Self::ensure_can_graduate(student_id, &student)?;
+			Self::do_graduate(
+				pending.owner,
+				student_id,
+				student,
+				pending.beneficiary,
+				pending.destination,
+			)
+		}
+
+		// EXPECT A STUDENT (is not called by user)
+		// Sent by the source chain itself ahead of `receive_student`, which accounts on that chain
+		// can send as well; only students announced for the sending account are received
+		#[pallet::call_index(47)]
 		#[pallet::weight(10_000)]
-		pub fn receive_student(
+		pub fn expect_student(
 			origin: OriginFor<T>,
-			student: Student<T>,
-			new_owner: T::AccountId,
+			source_id: u32,
+			sender: InteriorLocation,
 		) -> DispatchResult {
-			// Ensure SovereignAccount from XCM
-			ensure_signed_or_root(origin)?;
+			let para_id = Self::ensure_sibling_chain(origin)?;
+			ExpectedStudents::<T>::insert(para_id, source_id, sender);
+			Ok(())
+		}
+	}
 
-			let student_id = StudentCount::<T>::get();
-			StudentCount::<T>::put(student_id + 1);
+	impl<T: Config> Pallet<T> {
+		/// Salted commitment to a student's personal data: the hash of the SCALE encoding of
+		/// `(personal, salt)`.
+		pub fn commitment_of(personal: &PersonalData<T>, salt: &[u8]) -> T::Hash {
+			T::Hashing::hash_of(&(personal, salt))
+		}
 
-			Students::<T>::insert(student_id, student);
+		/// Hash identifying a graduate's record as sent to the destination, which can be checked
+		/// against the copy held there.
+		pub fn diploma_hash(student: &Student<T>) -> T::Hash {
+			T::Hashing::hash_of(student)
+		}
 
-			// Add to new owner's list
-			StudentsByOwner::<T>::try_mutate(&new_owner, |list| {
-				list.try_push(student_id)
-					.map_err(|_| Error::<T>::MaxStudentsReached)
-			})?;
+		/// Unix time in seconds according to [`Config::UnixTime`].
+		pub fn now() -> u64 {
+			T::UnixTime::now().as_secs()
+		}
+
+		/// `None` for [`Config::InstitutionRegistrar`], the signer otherwise.
+		fn ensure_signed_or_registrar(
+			origin: OriginFor<T>,
+		) -> Result<Option<T::AccountId>, DispatchError> {
+			match T::InstitutionRegistrar::try_origin(origin) {
+				Ok(_) => Ok(None),
+				Err(origin) => Ok(Some(ensure_signed(origin)?)),
+			}
+		}
 
-			Self::deposit_event(Event::StudentReceived { student_id });
+		fn ensure_can_graduate(student_id: u32, student: &Student<T>) -> DispatchResult {
+			ensure!(!student.status.has_graduated(), Error::<T>::AlreadyGraduated);
+			ensure!(
+				student.status.can_become(StudentStatus::Graduated),
+				Error::<T>::InvalidStatusTransition
+			);
+			// Institutions that lost their accreditation can no longer award degrees
+			if let Some(institution_id) = StudentInstitution::<T>::get(student_id) {
+				let institution = Institutions::<T>::get(institution_id)
+					.ok_or(Error::<T>::InstitutionNotFound)?;
+				ensure!(
+					institution.accreditation == Accreditation::Accredited,
+					Error::<T>::NotAccredited
+				);
+			}
+			ensure!(
+				Invoices::<T>::decode_len(student_id).unwrap_or(0) == 0,
+				Error::<T>::TuitionUnpaid
+			);
+			Ok(())
+		}
+
+		/// Institution `institution_id`, if `who` is one of its admins.
+		fn ensure_institution_admin(
+			who: &T::AccountId,
+			institution_id: u32,
+		) -> Result<Institution<T>, DispatchError> {
+			let institution =
+				Institutions::<T>::get(institution_id).ok_or(Error::<T>::InstitutionNotFound)?;
+			ensure!(institution.admins.contains(who), Error::<T>::NotInstitutionAdmin);
+			Ok(institution)
+		}
 
+		/// Like [`Self::ensure_institution_admin`], also requiring the institution to be
+		/// accredited.
+		fn ensure_accredited_admin(who: &T::AccountId, institution_id: u32) -> DispatchResult {
+			let institution = Self::ensure_institution_admin(who, institution_id)?;
+			ensure!(
+				institution.accreditation == Accreditation::Accredited,
+				Error::<T>::NotAccredited
+			);
 			Ok(())
 		}
 
+		/// Whether `who` is an admin of the institution of student `student_id`.
+		fn administers(who: &T::AccountId, student_id: u32) -> bool {
+			StudentInstitution::<T>::get(student_id)
+				.and_then(Institutions::<T>::get)
+				.is_some_and(|institution| institution.admins.contains(who))
+		}
 
-		// UPDATE STUDENT
-		#[pallet::call_index(4)]
-		#[pallet::weight(10_000)]
-		pub fn update_student(
-			origin: OriginFor<T>,
+		/// Location of a beneficiary, checking the destination will be able to map it.
+		fn beneficiary_location(
+			beneficiary: Option<Beneficiary>,
+		) -> Result<Option<Location>, DispatchError> {
+			let beneficiary = beneficiary.map(Location::from);
+			if let Some(location) = &beneficiary {
+				ensure!(
+					T::LocationToAccountId::convert_location(location).is_some(),
+					Error::<T>::BeneficiaryNotConvertible
+				);
+			}
+			Ok(beneficiary)
+		}
+
+		/// Take the job offer of `employer` on `para_id` to `student_id`, checking `who` is the
+		/// student.
+		fn take_job_offer(
+			who: &T::AccountId,
 			student_id: u32,
-			name: Vec<u8>,
-			surname: Vec<u8>,
-			age: u32,
-			gender: Gender,
+			para_id: u32,
+			employer: u32,
+		) -> Result<JobOffer<T>, DispatchError> {
+			ensure!(
+				StudentAccounts::<T>::get(student_id).as_ref() == Some(who),
+				Error::<T>::NotStudentAccount
+			);
+			JobOffers::<T>::try_mutate(student_id, |offers| {
+				let index = offers
+					.iter()
+					.position(|offer| offer.para_id == para_id && offer.employer == employer)
+					.ok_or(Error::<T>::NoJobOffer)?;
+				Ok(offers.remove(index))
+			})
+		}
+
+		fn position_from(
+			title: Vec<u8>,
+			department: Vec<u8>,
+			contract: ContractType,
+		) -> Result<Position<T>, DispatchError> {
+			Ok(Position {
+				title: title.try_into().map_err(|_| Error::<T>::PositionTooLong)?,
+				department: department.try_into().map_err(|_| Error::<T>::PositionTooLong)?,
+				contract,
+			})
+		}
+
+		/// Where student `student_id` graduates to, as set by its institution.
+		fn graduation_destination(student_id: u32) -> GraduationDestination {
+			StudentInstitution::<T>::get(student_id)
+				.and_then(Institutions::<T>::get)
+				.and_then(|institution| institution.graduation_destination)
+				.unwrap_or(GraduationDestination {
+					para_id: T::GraduationDestinationPara::get(),
+					institution: T::GraduationDestinationInstitution::get(),
+				})
+		}
+
+		/// Move `student` to the status `to`, stamping the time of the change.
+		fn transition(
+			student_id: u32,
+			student: &mut Student<T>,
+			to: StudentStatus,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+			let from = student.status;
+			ensure!(from.can_become(to), Error::<T>::InvalidStatusTransition);
 
-			// Ensure the caller owns this student
-			let owned_ids = StudentsByOwner::<T>::get(&who);
-			ensure!(owned_ids.contains(&student_id), Error::<T>::NotStudentOwner);
+			Self::record_change(student_id, Change::StatusChanged { from, to }, Some(student));
+			let at = Self::now();
+			student.status = to;
+			student.status_since = at;
+			Self::refresh_item(student_id, student)?;
 
-			// Get the student and ensure they exist
-			let mut student = Students::<T>::get(student_id)
-				.ok_or(Error::<T>::StudentNotFound)?;
+			Self::deposit_event(Event::StudentStatusChanged { student_id, from, to, at });
 
-			// Convert name and surname to bounded vecs
-			let name: BoundedVec<_, T::MaxNameLen> =
-				name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
-			let surname: BoundedVec<_, T::MaxSurnameLen> =
-				surname.try_into().map_err(|_| Error::<T>::SurnameTooLong)?;
+			Ok(())
+		}
 
-			// Update student fields
-			student.name = name;
-			student.surname = surname;
-			student.age = age;
-			student.gender = gender;
+		/// Change the status of a stored student controlled by `who`.
+		fn change_status(who: &T::AccountId, student_id: u32, to: StudentStatus) -> DispatchResult {
+			Self::ensure_controls(who, student_id)?;
+			Students::<T>::try_mutate(student_id, |student| {
+				let student = student.as_mut().ok_or(Error::<T>::StudentNotFound)?;
+				Self::transition(student_id, student, to)
+			})
+		}
 
-			// Save updated student
-			Students::<T>::insert(student_id, student);
+		pub fn owns(who: &T::AccountId, student_id: u32) -> bool {
+			StudentsByOwner::<T>::contains_key(who, student_id)
+		}
 
-			// Emit event
-			Self::deposit_event(Event::StudentUpdated { who, student_id });
+		/// Ensure `who` owns student `student_id` and, if an institution enrolled it here, is
+		/// still one of its admins. Received students belong to whoever the source chain sent
+		/// them to.
+		fn ensure_controls(who: &T::AccountId, student_id: u32) -> DispatchResult {
+			ensure!(Self::owns(who, student_id), Error::<T>::NotStudentOwner);
+			if StudentInstitution::<T>::contains_key(student_id) &&
+				!ReceivedFrom::<T>::contains_key(student_id)
+			{
+				ensure!(Self::administers(who, student_id), Error::<T>::NotInstitutionAdmin);
+			}
+			Ok(())
+		}
+
+		/// Collection and id of the local item of the student `source_id` received from
+		/// `para_id`, for minting the copy of an item reserved there.
+		pub fn received_item(para_id: u32, source_id: u32) -> Option<(u32, u32)> {
+			let student_id = ReceivedIndex::<T>::get(para_id, source_id)?;
+			Some((StudentInstitution::<T>::get(student_id)?, student_id))
+		}
+
+		/// Metadata for the item of a student: its encoded record.
+		pub fn item_metadata(student_id: u32) -> Option<Vec<u8>> {
+			Students::<T>::get(student_id).map(|student| student.encode())
+		}
 
+		/// Give `student_id` to `owner`, within [`Config::MaxStudentsPerOwner`].
+		fn add_owned(owner: &T::AccountId, student_id: u32) -> DispatchResult {
+			OwnedStudentCount::<T>::try_mutate(owner, |count| {
+				ensure!(*count < T::MaxStudentsPerOwner::get(), Error::<T>::MaxStudentsReached);
+				*count += 1;
+				Ok::<_, DispatchError>(())
+			})?;
+			StudentsByOwner::<T>::insert(owner, student_id, ());
+			StudentOwner::<T>::insert(student_id, owner);
 			Ok(())
 		}
 
+		fn remove_owned(owner: &T::AccountId, student_id: u32) -> DispatchResult {
+			StudentsByOwner::<T>::take(owner, student_id).ok_or(Error::<T>::NotStudentOwner)?;
+			OwnedStudentCount::<T>::mutate_exists(owner, |count| {
+				*count = count.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+			});
+			StudentOwner::<T>::remove(student_id);
+			Ok(())
+		}
 
-		// DELETE ANY STUDENT (admin only - for received students)
-		#[pallet::call_index(6)]
-		#[pallet::weight(10_000)]
-		pub fn delete_any_student(
-			origin: OriginFor<T>,
+		/// Allocate the next local student id.
+		pub(crate) fn next_student_id() -> Result<u32, DispatchError> {
+			StudentCount::<T>::try_mutate(|count| {
+				let student_id = *count;
+				*count = count.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+				Ok(student_id)
+			})
+		}
+
+		/// Key identifying the person behind a record in [`StudentIdentities`]: the national id
+		/// hash if known, else the full name and date of birth, or the commitment of records
+		/// without plaintext data. `None` for erased records.
+		pub fn identity_of(student: &Student<T>) -> Option<T::Hash> {
+			match (&student.personal, &student.commitment) {
+				(Some(personal), _) => Some(match personal.national_id_hash {
+					Some(national_id) => T::Hashing::hash_of(&(b"national_id", national_id)),
+					None => T::Hashing::hash_of(&(
+						b"name_dob",
+						&personal.given_name,
+						&personal.middle_name,
+						&personal.family_name,
+						personal.date_of_birth,
+					)),
+				}),
+				(None, Some(commitment)) =>
+					Some(T::Hashing::hash_of(&(b"commitment", commitment.hash))),
+				(None, None) => None,
+			}
+		}
+
+		/// Register `student` under its identity, failing if another record already has it.
+		fn index_student(student_id: u32, student: &Student<T>) -> DispatchResult {
+			if let Some(identity) = Self::identity_of(student) {
+				ensure!(
+					!StudentIdentities::<T>::contains_key(identity),
+					Error::<T>::StudentAlreadyExists
+				);
+				StudentIdentities::<T>::insert(identity, student_id);
+			}
+			Ok(())
+		}
+
+		/// Release the identity of `student`, unless another student holds it, e.g. the original
+		/// of a duplicate left unindexed by [`crate::migrations`].
+		fn unindex_student(student_id: u32, student: &Student<T>) {
+			if let Some(identity) = Self::identity_of(student) {
+				if StudentIdentities::<T>::get(identity) == Some(student_id) {
+					StudentIdentities::<T>::remove(identity);
+				}
+			}
+		}
+
+		/// Today's date according to [`Config::UnixTime`].
+		pub fn today() -> Date {
+			Date::from_unix_secs(Self::now())
+		}
+
+		/// Check and bound submitted personal data.
+		pub fn validate_personal_data(
+			data: PersonalDataInput<T::Hash>,
+		) -> Result<PersonalData<T>, DispatchError> {
+			ensure!(!data.given_name.is_empty(), Error::<T>::EmptyName);
+			ensure!(!data.family_name.is_empty(), Error::<T>::EmptySurname);
+			ensure!(
+				core::str::from_utf8(&data.given_name).is_ok() &&
+					core::str::from_utf8(&data.middle_name).is_ok(),
+				Error::<T>::NameNotUtf8
+			);
+			ensure!(core::str::from_utf8(&data.family_name).is_ok(), Error::<T>::SurnameNotUtf8);
+			Self::validate_date_of_birth(data.date_of_birth)?;
+
+			Ok(PersonalData {
+				given_name: data.given_name.try_into().map_err(|_| Error::<T>::NameTooLong)?,
+				middle_name: data.middle_name.try_into().map_err(|_| Error::<T>::NameTooLong)?,
+				family_name: data.family_name.try_into().map_err(|_| Error::<T>::SurnameTooLong)?,
+				date_of_birth: data.date_of_birth,
+				gender: data.gender,
+				national_id_hash: data.national_id_hash,
+				email_hash: data.email_hash,
+			})
+		}
+
+		/// Check the fields changed by `delta` like [`Self::validate_personal_data`] does.
+		pub fn validate_delta(delta: &PersonalDataDelta<T>) -> DispatchResult {
+			let utf8 =
+				|name: Option<&Vec<u8>>| name.is_none_or(|n| core::str::from_utf8(n).is_ok());
+			ensure!(delta.given_name.as_ref().is_none_or(|n| !n.is_empty()), Error::<T>::EmptyName);
+			ensure!(
+				delta.family_name.as_ref().is_none_or(|n| !n.is_empty()),
+				Error::<T>::EmptySurname
+			);
+			ensure!(
+				utf8(delta.given_name.as_deref()) && utf8(delta.middle_name.as_deref()),
+				Error::<T>::NameNotUtf8
+			);
+			ensure!(utf8(delta.family_name.as_deref()), Error::<T>::SurnameNotUtf8);
+			if let Some(date_of_birth) = delta.date_of_birth {
+				Self::validate_date_of_birth(date_of_birth)?;
+			}
+			Ok(())
+		}
+
+		fn validate_date_of_birth(date_of_birth: Date) -> DispatchResult {
+			ensure!(date_of_birth.is_valid(), Error::<T>::InvalidDateOfBirth);
+			let age = date_of_birth
+				.years_until(&Self::today())
+				.ok_or(Error::<T>::DateOfBirthInFuture)?;
+			ensure!(age <= T::MaxAge::get(), Error::<T>::DateOfBirthTooOld);
+			Ok(())
+		}
+
+		/// Send `call` to be dispatched on the sibling parachain `para_id`, optionally on behalf of
+		/// `descend_to` below this chain, and return the destination.
+		pub(crate) fn send_call(
+			para_id: u32,
+			descend_to: Option<InteriorLocation>,
+			call: Call<T>,
+		) -> Result<Location, DispatchError> {
+			Self::send_transact(para_id, None, descend_to, call, None, None)
+		}
+
+		/// Like [`Self::send_call`], having the destination report the outcome of the call to
+		/// `report` if given. `announce` is dispatched on behalf of this chain before descending,
+		/// to vouch for `call`. An `item` reserved on this chain is deposited into its beneficiary
+		/// on the destination once the call is dispatched; it stays in the holding, to be
+		/// trapped, if the call fails.
+		fn send_transact(
+			para_id: u32,
+			announce: Option<Call<T>>,
+			descend_to: Option<InteriorLocation>,
+			call: Call<T>,
+			report: Option<QueryResponseInfo>,
+			item: Option<(Asset, Location)>,
+		) -> Result<Location, DispatchError> {
+			let destination = Location::new(1, [Parachain(para_id)]);
+
+			// Build XCM message
+			let mut message =
+				vec![UnpaidExecution { weight_limit: WeightLimit::Unlimited, check_origin: None }];
+			if let Some((asset, _)) = &item {
+				message.push(ReserveAssetDeposited(asset.clone().into()));
+			}
+			if let Some(announce) = announce {
+				message.push(Self::transact(announce));
+			}
+			if let Some(interior) = descend_to {
+				message.push(DescendOrigin(interior));
+			}
+			message.push(Self::transact(call));
+			if let Some((_, beneficiary)) = item {
+				message.push(DepositAsset { assets: Wild(AllCounted(1)), beneficiary });
+			}
+			if let Some(info) = report {
+				message.push(ReportTransactStatus(info));
+			}
+
+			// Send XCM message
+			polkadot_sdk::staging_xcm::latest::send_xcm::<T::XcmSender>(
+				destination.clone(),
+				Xcm(message),
+			)
+			.map_err(|_| Error::<T>::XcmSendFailed)?;
+
+			Ok(destination)
+		}
+
+		fn transact(call: Call<T>) -> Instruction<()> {
+			Transact {
+				origin_kind: OriginKind::Xcm,
+				fallback_max_weight: Some(Weight::from_parts(1_000_000_000, 64 * 1024)),
+				call: <T as Config>::RuntimeCall::from(call).encode().into(),
+			}
+		}
+
+		/// Id of the sibling parachain an XCM origin belongs to.
+		fn sibling_para_id(location: &Location) -> Result<u32, DispatchError> {
+			match location.unpack() {
+				(1, [Parachain(para_id), ..]) => Ok(*para_id),
+				_ => Err(Error::<T>::NotFromSiblingChain.into()),
+			}
+		}
+
+		/// Id of the sibling parachain `origin` is. Calls sent by this pallet without
+		/// descending into an account come from the sibling itself, which accounts on it cannot
+		/// impersonate.
+		fn ensure_sibling_chain(origin: OriginFor<T>) -> Result<u32, DispatchError> {
+			let location = T::XcmOrigin::ensure_origin(origin)?;
+			match location.unpack() {
+				(1, [Parachain(para_id)]) => Ok(*para_id),
+				_ => Err(Error::<T>::NotFromSiblingChain.into()),
+			}
+		}
+
+		/// Graduate a student owned by `who`, once the committee of its institution approves if
+		/// it has one, see [`Pallet::approve_graduation`].
+		fn graduate_once_approved(
+			who: T::AccountId,
 			student_id: u32,
+			student: Student<T>,
+			beneficiary: Option<Location>,
+			destination: GraduationDestination,
 		) -> DispatchResult {
-			ensure_signed_or_root(origin)?;
+			let committee =
+				StudentInstitution::<T>::get(student_id).and_then(GraduationCommittees::<T>::get);
+			if committee.is_none() {
+				return Self::do_graduate(who, student_id, student, beneficiary, destination)
+			}
 
-			// Ensure the student exists
-			ensure!(Students::<T>::contains_key(student_id), Error::<T>::StudentNotFound);
+			let now = frame_system::Pallet::<T>::block_number();
+			if let Some(pending) = PendingGraduations::<T>::get(student_id) {
+				ensure!(pending.expires_at < now, Error::<T>::GraduationAlreadyPending);
+			}
+			let expires_at = now.saturating_add(T::GraduationApprovalPeriod::get());
+			PendingGraduations::<T>::insert(
+				student_id,
+				PendingGraduation {
+					owner: who,
+					beneficiary,
+					destination,
+					approvals: Default::default(),
+					expires_at,
+				},
+			);
+
+			Self::deposit_event(Event::GraduationApprovalRequested { student_id, expires_at });
+
+			Ok(())
+		}
+
+		/// Send a student owned by `who` to the graduation destination and remove it locally.
+		fn do_graduate(
+			who: T::AccountId,
+			student_id: u32,
+			mut student: Student<T>,
+			beneficiary: Option<Location>,
+			destination: GraduationDestination,
+		) -> DispatchResult {
+			// Graduate, and hand the record over to the destination
+			Self::transition(student_id, &mut student, StudentStatus::Graduated)?;
+			Self::transition(student_id, &mut student, StudentStatus::Transferred)?;
+
+			// Descend into the owner's account so the destination derives the owner from the
+			// XCM origin; the owner is not part of the call
+			let owner_location = T::AccountIdToLocation::try_convert(&who)
+				.map_err(|_| Error::<T>::AccountNotConvertible)?;
+			ensure!(owner_location.parent_count() == 0, Error::<T>::AccountNotConvertible);
+
+			// Send the student to the destination parachain, together with its history
+			let GraduationDestination { para_id: dest_para_id, institution } = destination;
+			Self::record_change(
+				student_id,
+				Change::Sent { destination_para: dest_para_id },
+				Some(&student),
+			);
+
+			// The item of the student goes along, to whoever the destination makes the owner
+			let destination = Location::new(1, [Parachain(dest_para_id)]);
+			let diploma_hash = Self::diploma_hash(&student);
+			let institution_id = StudentInstitution::<T>::get(student_id);
+			let item = match Self::reserve_item(student_id, &destination, diploma_hash)? {
+				Some(asset) => {
+					let owner = match &beneficiary {
+						Some(beneficiary) => beneficiary.clone(),
+						None => owner_location
+							.clone()
+							.reanchored(&destination, &T::UniversalLocation::get())
+							.map_err(|_| Error::<T>::AccountNotConvertible)?,
+					};
+					Some((asset, owner))
+				},
+				None => None,
+			};
+
+			let destination = Self::send_transact(
+				dest_para_id,
+				Some(Call::<T>::expect_student {
+					source_id: student_id,
+					sender: owner_location.interior().clone(),
+				}),
+				Some(owner_location.interior().clone()),
+				Call::<T>::receive_student {
+					student: student.clone(),
+					source_id: student_id,
+					beneficiary: beneficiary.clone(),
+					history: StudentHistory::<T>::get(student_id),
+					institution,
+				},
+				None,
+				item,
+			)?;
+
+			// Payments of a stipend continue on the destination, if awarded so
+			Self::continue_stipend(student_id, dest_para_id)?;
+
+			// Remove student from this parachain after successful transfer
+			Self::remove_owned(&who, student_id)?;
+
+			Self::remove_record(student_id);
+			Alumni::<T>::insert(
+				student_id,
+				AlumniRecord {
+					destination_para: dest_para_id,
+					institution_id,
+					graduated_by: who.clone(),
+					graduated_at: frame_system::Pallet::<T>::block_number(),
+					diploma_hash,
+				},
+			);
+
+			Self::deposit_event(Event::StudentGraduatedAndTransferred {
+				who,
+				student_id,
+				destination,
+				beneficiary,
+			});
+
+			Ok(())
+		}
+
+		/// Append a change of `student_id` to its history, dropping the oldest entry when full.
+		/// `previous` is the record before the change.
+		fn record_change(student_id: u32, change: Change, previous: Option<&Student<T>>) {
+			let entry = HistoryEntry {
+				at: Self::now(),
+				change,
+				previous: previous.map(T::Hashing::hash_of),
+			};
+			StudentHistory::<T>::mutate(student_id, |history| {
+				if history.is_full() {
+					history.remove(0);
+				}
+				let _ = history.try_push(entry);
+			});
+		}
+
+		/// Move a student to [`DeletedStudents`] until the grace period is over. The caller
+		/// removes it from its owner's list.
+		fn soft_delete(
+			student_id: u32,
+			owner: Option<T::AccountId>,
+		) -> Result<BlockNumberFor<T>, DispatchError> {
+			let student = Students::<T>::take(student_id).ok_or(Error::<T>::StudentNotFound)?;
+			PendingTransfers::<T>::remove(student_id);
+
+			let purge_at = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::DeletionGracePeriod::get());
+			PurgeAgenda::<T>::try_mutate(purge_at, |ids| ids.try_push(student_id))
+				.map_err(|_| Error::<T>::TooManyDeletions)?;
+
+			Self::record_change(student_id, Change::Deleted, Some(&student));
+			DeletedStudents::<T>::insert(student_id, DeletedStudent { student, owner, purge_at });
+
+			Ok(purge_at)
+		}
+
+		/// Purge the deleted students due up to `now`, within `remaining_weight`.
+		pub(crate) fn purge_expired(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let db = T::DbWeight::get();
+			// Cursor, plus the agenda of each block visited
+			let mut used = db.reads_writes(1, 1);
+			let per_block = db.reads_writes(1, 1);
+			// See `purge_record`, burning the item included
+			let per_student = db.reads_writes(7, 24);
+			if remaining_weight.any_lt(used.saturating_add(per_block)) {
+				return Weight::zero()
+			}
+
+			let mut block = NextPurgeBlock::<T>::get();
+			if block.is_zero() {
+				// Nothing can be due before the first visit
+				block = now;
+			}
+			while block <= now && !remaining_weight.any_lt(used.saturating_add(per_block)) {
+				used.saturating_accrue(per_block);
+				let mut ids = PurgeAgenda::<T>::take(block);
+				while let Some(student_id) = ids.last().copied() {
+					if remaining_weight.any_lt(used.saturating_add(per_student)) {
+						break
+					}
+					used.saturating_accrue(per_student);
+					Self::purge_record(student_id);
+					ids.pop();
+				}
+				if !ids.is_empty() {
+					PurgeAgenda::<T>::insert(block, ids);
+					break
+				}
+				block.saturating_inc();
+			}
+			NextPurgeBlock::<T>::put(block);
+
+			used
+		}
 
-			// Remove the student record
-			Students::<T>::remove(student_id);
+		/// Drop the verification requests timed out up to `now`, within `remaining_weight`.
+		pub(crate) fn expire_verifications(
+			now: BlockNumberFor<T>,
+			remaining_weight: Weight,
+		) -> Weight {
+			let db = T::DbWeight::get();
+			// Cursor, plus the agenda of each block visited
+			let mut used = db.reads_writes(1, 1);
+			let per_block = db.reads_writes(1, 1);
+			// The request and the count of its student
+			let per_query = db.reads_writes(2, 2);
+			if remaining_weight.any_lt(used.saturating_add(per_block)) {
+				return Weight::zero()
+			}
 
-			// Try to remove from owner's list if they have an owner
-			// This iterates through all possible owners - not efficient but works for small datasets
-			for (owner, mut owned_ids) in StudentsByOwner::<T>::iter() {
-				if let Some(index) = owned_ids.iter().position(|id| *id == student_id) {
-					owned_ids.swap_remove(index);
-					StudentsByOwner::<T>::insert(&owner, owned_ids);
-					break;
+			let mut block = NextVerificationExpiry::<T>::get();
+			if block.is_zero() {
+				// Nothing can be due before the first visit
+				block = now;
+			}
+			while block <= now && !remaining_weight.any_lt(used.saturating_add(per_block)) {
+				used.saturating_accrue(per_block);
+				let mut ids = VerificationAgenda::<T>::take(block);
+				while let Some(query_id) = ids.last().copied() {
+					if remaining_weight.any_lt(used.saturating_add(per_query)) {
+						break
+					}
+					used.saturating_accrue(per_query);
+					// Answered requests are already gone
+					if let Some(student_id) = Self::settle_verification(query_id) {
+						Self::deposit_event(Event::VerificationExpired { student_id, query_id });
+					}
+					ids.pop();
 				}
+				if !ids.is_empty() {
+					VerificationAgenda::<T>::insert(block, ids);
+					break
+				}
+				block.saturating_inc();
+			}
+			NextVerificationExpiry::<T>::put(block);
+
+			used
+		}
+
+		/// Remove a pending verification request, returning the student it is about.
+		fn settle_verification(query_id: QueryId) -> Option<u32> {
+			let student_id = PendingVerifications::<T>::take(query_id)?;
+			PendingVerificationCount::<T>::mutate_exists(student_id, |count| {
+				*count = count.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0);
+			});
+			Some(student_id)
+		}
+
+		/// Remove a deleted student for good.
+		fn purge_record(student_id: u32) {
+			if let Some(deleted) = DeletedStudents::<T>::take(student_id) {
+				Self::unindex_student(student_id, &deleted.student);
+			}
+			// Only this pallet moves the items, nothing can keep them from being burnt
+			let _ = Self::burn_item(student_id);
+			Self::remove_links(student_id);
+			Self::deposit_event(Event::StudentPurged { student_id });
+		}
+
+		/// Remove a student record together with its cross-chain links and pending requests.
+		fn remove_record(student_id: u32) {
+			if let Some(student) = Students::<T>::take(student_id) {
+				Self::unindex_student(student_id, &student);
+			}
+			Self::remove_links(student_id);
+		}
+
+		fn remove_links(student_id: u32) {
+			StudentAccounts::<T>::remove(student_id);
+			PendingTransfers::<T>::remove(student_id);
+			StudentHistory::<T>::remove(student_id);
+			StudentInstitution::<T>::remove(student_id);
+			Employments::<T>::remove(student_id);
+			JobOffers::<T>::remove(student_id);
+			ReceivedDiplomas::<T>::remove(student_id);
+			Verifications::<T>::remove(student_id);
+			PendingVerificationCount::<T>::remove(student_id);
+			RevokedDiplomas::<T>::remove(student_id);
+			LocalEdits::<T>::remove(student_id);
+			Invoices::<T>::remove(student_id);
+			PendingGraduations::<T>::remove(student_id);
+			if let Some(origin) = ReceivedFrom::<T>::take(student_id) {
+				ReceivedIndex::<T>::remove(origin.para_id, origin.student_id);
+			}
+		}
+
+		/// Write `student` into the metadata of its item. Students outside institutions, and the
+		/// ones enrolled before students had items, have no item.
+		fn refresh_item(student_id: u32, student: &Student<T>) -> DispatchResult {
+			match StudentInstitution::<T>::get(student_id) {
+				Some(institution_id) if T::Items::owner(institution_id, student_id).is_some() =>
+					T::Items::set_metadata(institution_id, student_id, &student.encode()),
+				_ => Ok(()),
+			}
+		}
+
+		fn burn_item(student_id: u32) -> DispatchResult {
+			match StudentInstitution::<T>::get(student_id) {
+				Some(institution_id) if T::Items::owner(institution_id, student_id).is_some() =>
+					T::Items::burn(institution_id, student_id),
+				_ => Ok(()),
+			}
+		}
+
+		/// Hand the item of a student over to the sovereign account of `destination`, which
+		/// keeps it as the reserve of the copy minted there. Returns the item as seen from
+		/// `destination`.
+		///
+		/// The record itself travels in the call, so the reserved item only keeps `diploma_hash`.
+		fn reserve_item(
+			student_id: u32,
+			destination: &Location,
+			diploma_hash: T::Hash,
+		) -> Result<Option<Asset>, DispatchError> {
+			let Some(institution_id) = StudentInstitution::<T>::get(student_id) else {
+				return Ok(None)
+			};
+			if T::Items::owner(institution_id, student_id).is_none() {
+				return Ok(None)
+			}
+
+			T::Items::set_metadata(institution_id, student_id, &diploma_hash.encode())?;
+			let sovereign = T::LocationToAccountId::convert_location(destination)
+				.ok_or(Error::<T>::AccountNotConvertible)?;
+			T::Items::transfer(institution_id, student_id, &sovereign)?;
+
+			let asset = T::Items::asset(institution_id, student_id)
+				.reanchored(destination, &T::UniversalLocation::get())
+				.map_err(|_| Error::<T>::XcmSendFailed)?;
+			Ok(Some(asset))
+		}
+
+		/// Wipe the plaintext personal data of a stored student. Erased students no longer block
+		/// registering the same person again.
+		fn erase_record(student_id: u32) -> DispatchResult {
+			// The metadata of the item holds the record, so it is wiped as well
+			let erase = |student: &mut Student<T>| {
+				Self::unindex_student(student_id, student);
+				Self::record_change(student_id, Change::Erased, Some(student));
+				student.erase_personal_data();
+				Self::refresh_item(student_id, student)
+			};
+			// Deleted students still waiting for their purge are erased too
+			if DeletedStudents::<T>::contains_key(student_id) {
+				DeletedStudents::<T>::try_mutate(student_id, |deleted| match deleted {
+					Some(deleted) => erase(&mut deleted.student),
+					None => Ok(()),
+				})?;
+			} else {
+				Students::<T>::try_mutate(student_id, |student| {
+					erase(student.as_mut().ok_or(Error::<T>::StudentNotFound)?)
+				})?;
 			}
 
-			Self::deposit_event(Event::StudentDeletedByAdmin { student_id });
+			Self::deposit_event(Event::StudentDataErased { student_id });
+
+			Ok(())
+		}
+
+		/// Account institution `institution_id` collects tuition in, and keeps its scholarship
+		/// fund in.
+		pub fn institution_account(institution_id: u32) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating(institution_id)
+		}
+
+		/// Remove invoice `invoice_id` from the unpaid invoices of a student.
+		fn take_invoice(student_id: u32, invoice_id: u32) -> Result<Invoice<T>, DispatchError> {
+			Invoices::<T>::try_mutate(student_id, |invoices| {
+				let index = invoices
+					.iter()
+					.position(|invoice| invoice.id == invoice_id)
+					.ok_or(Error::<T>::NoInvoice)?;
+				Ok(invoices.remove(index))
+			})
+		}
+
+		fn remove_stipend(student_id: u32, stipend: &Stipend<T>) {
+			Stipends::<T>::remove(student_id);
+			StipendAgenda::<T>::mutate(stipend.next_payment, |ids| {
+				ids.retain(|id| *id != student_id)
+			});
+		}
+
+		/// Move the stipend of a student graduating to `para_id` to its account there, unless the
+		/// stipend ends with the graduation.
+		fn continue_stipend(student_id: u32, para_id: u32) -> DispatchResult {
+			let Some(mut stipend) = Stipends::<T>::get(student_id) else { return Ok(()) };
+			if !stipend.after_graduation {
+				Self::remove_stipend(student_id, &stipend);
+				Self::deposit_event(Event::StipendEnded { student_id });
+				return Ok(())
+			}
 
+			if stipend.payee == StipendPayee::Student {
+				// The account linked last has the same location on the destination, and without
+				// one there is nobody to pay there
+				let Some(account) = StudentAccounts::<T>::get(student_id) else {
+					Self::remove_stipend(student_id, &stipend);
+					Self::deposit_event(Event::StipendEnded { student_id });
+					return Ok(())
+				};
+				let beneficiary = T::AccountIdToLocation::try_convert(&account)
+					.map_err(|_| Error::<T>::AccountNotConvertible)?;
+				stipend.payee = StipendPayee::Graduate { para_id, beneficiary };
+				Stipends::<T>::insert(student_id, stipend);
+			}
 			Ok(())
 		}
+
+		/// Pay the stipends due in `now` and schedule their next payments. Failed payments are
+		/// retried in the next period.
+		pub(crate) fn pay_stipends(now: BlockNumberFor<T>) -> Weight {
+			let db = T::DbWeight::get();
+			let ids = StipendAgenda::<T>::take(now);
+			// The stipend, its student or deleted student, its account, the fund and the payee, and
+			// the agenda of the next payment
+			let per_stipend = db.reads_writes(7, 4);
+			let mut sends = 0u64;
+
+			let next_payment = now.saturating_add(T::StipendPeriod::get());
+			for &student_id in &ids {
+				let Some(mut stipend) = Stipends::<T>::get(student_id) else { continue };
+
+				// Stipends of deleted students are paused until they are restored, and end once
+				// they are purged
+				let paused = stipend.payee == StipendPayee::Student &&
+					!Students::<T>::contains_key(student_id);
+				if paused && !DeletedStudents::<T>::contains_key(student_id) {
+					Stipends::<T>::remove(student_id);
+					Self::deposit_event(Event::StipendEnded { student_id });
+					continue
+				}
+
+				if !paused {
+					if matches!(stipend.payee, StipendPayee::Graduate { .. }) {
+						sends.saturating_inc();
+					}
+					match Self::pay_stipend(student_id, &stipend) {
+						Ok(destination) => Self::deposit_event(Event::StipendPaid {
+							student_id,
+							amount: stipend.amount,
+							destination,
+						}),
+						Err(error) =>
+							Self::deposit_event(Event::StipendPaymentFailed { student_id, error }),
+					}
+				}
+
+				// Awards of this block come later, so the next payments fit, unless the period
+				// changed in between
+				if StipendAgenda::<T>::try_mutate(next_payment, |ids| ids.try_push(student_id))
+					.is_err()
+				{
+					Stipends::<T>::remove(student_id);
+					Self::deposit_event(Event::StipendEnded { student_id });
+					continue
+				}
+				stipend.next_payment = next_payment;
+				Stipends::<T>::insert(student_id, stipend);
+			}
+
+			db.reads_writes(1, 1)
+				.saturating_add(per_stipend.saturating_mul(ids.len() as u64))
+				.saturating_add(T::StipendSendWeight::get().saturating_mul(sends))
+		}
+
+		/// Pay a stipend from the scholarship fund of its institution. Graduates on other chains
+		/// are paid with a reserve transfer, and the chain is returned.
+		fn pay_stipend(
+			student_id: u32,
+			stipend: &Stipend<T>,
+		) -> Result<Option<Location>, DispatchError> {
+			let fund = Self::institution_account(stipend.institution);
+			match &stipend.payee {
+				StipendPayee::Student => {
+					let account = StudentAccounts::<T>::get(student_id)
+						.ok_or(Error::<T>::NoStudentAccount)?;
+					T::Currency::transfer(&fund, &account, stipend.amount, Preservation::Preserve)?;
+					Ok(None)
+				},
+				StipendPayee::Graduate { para_id, beneficiary } => with_storage_layer(|| {
+					// The sovereign account of the destination holds the tokens as their reserve
+					let destination = Location::new(1, [Parachain(*para_id)]);
+					let sovereign = T::LocationToAccountId::convert_location(&destination)
+						.ok_or(Error::<T>::AccountNotConvertible)?;
+					T::Currency::transfer(
+						&fund,
+						&sovereign,
+						stipend.amount,
+						Preservation::Preserve,
+					)?;
+
+					let amount: u128 = stipend.amount.unique_saturated_into();
+					let asset = Asset::from((Here, amount))
+						.reanchored(&destination, &T::UniversalLocation::get())
+						.map_err(|_| Error::<T>::XcmSendFailed)?;
+					let message = Xcm(vec![
+						UnpaidExecution {
+							weight_limit: WeightLimit::Unlimited,
+							check_origin: None,
+						},
+						ReserveAssetDeposited(asset.into()),
+						ClearOrigin,
+						DepositAsset {
+							assets: Wild(AllCounted(1)),
+							beneficiary: beneficiary.clone(),
+						},
+					]);
+					polkadot_sdk::staging_xcm::latest::send_xcm::<T::XcmSender>(
+						destination.clone(),
+						message,
+					)
+					.map_err(|_| Error::<T>::XcmSendFailed)?;
+
+					Ok(Some(destination))
+				}),
+			}
+		}
 	}
 }
\ No newline at end of file
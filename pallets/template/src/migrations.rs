@@ -0,0 +1,124 @@
+//! Storage migrations of the student registry.
+
+use crate::pallet::{Config, Gender, Pallet};
+use frame::{
+	deps::frame_support::{migrations::VersionedMigration, storage_alias},
+	prelude::*,
+	traits::UncheckedOnRuntimeUpgrade,
+};
+
+/// Migrates the students and their owners from the unversioned layout.
+///
+/// - Name, surname and age become structured personal data. The date of birth is approximated as
+///   January 1st of the current year minus the stored age.
+/// - The `has_graduated` flag becomes a [`crate::pallet::StudentStatus`], dated at the time of the
+///   migration.
+/// - The [`crate::pallet::StudentIdentities`] uniqueness index is built. When several records share
+///   an identity only the one with the lowest id is indexed, the others are left in place for their
+///   owners to clean up.
+/// - The per-owner student lists move into the [`crate::pallet::StudentsByOwner`] double map,
+///   counted in [`crate::pallet::OwnedStudentCount`] and indexed in
+///   [`crate::pallet::StudentOwner`]. The limit of [`Config::MaxStudentsPerOwner`] is not enforced
+///   on existing students.
+pub mod v1 {
+	use super::*;
+	use crate::{
+		date::Date,
+		pallet::{
+			OwnedStudentCount, PersonalData, Student, StudentIdentities, StudentOwner,
+			StudentStatus, Students, StudentsByOwner,
+		},
+	};
+
+	/// Layout of [`Student`] at storage version 0.
+	#[derive(Encode, Decode)]
+	pub struct OldStudent<T: Config> {
+		pub name: BoundedVec<u8, T::MaxNameLen>,
+		pub surname: BoundedVec<u8, T::MaxSurnameLen>,
+		pub age: u32,
+		pub gender: Gender,
+		pub has_graduated: bool,
+	}
+
+	/// [`StudentsByOwner`] at storage version 0.
+	#[storage_alias]
+	pub type OldStudentsByOwner<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		BoundedVec<u32, ConstU32<100>>,
+		ValueQuery,
+	>;
+
+	pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let today = Pallet::<T>::today();
+			let now = Pallet::<T>::now();
+			let mut reads = 1u64;
+			let mut writes = 0u64;
+
+			Students::<T>::translate::<OldStudent<T>, _>(|_, old| {
+				reads.saturating_inc();
+				writes.saturating_inc();
+				Some(Student {
+					personal: Some(PersonalData {
+						given_name: old.name,
+						middle_name: Default::default(),
+						family_name: old.surname,
+						date_of_birth: Date {
+							year: today.year.saturating_sub(old.age.try_into().unwrap_or(u16::MAX)),
+							month: 1,
+							day: 1,
+						},
+						gender: old.gender,
+						national_id_hash: None,
+						email_hash: None,
+					}),
+					status: if old.has_graduated {
+						StudentStatus::Graduated
+					} else {
+						StudentStatus::Enrolled
+					},
+					status_since: now,
+					commitment: None,
+				})
+			});
+
+			let mut students = Students::<T>::iter().collect::<Vec<_>>();
+			students.sort_by_key(|(id, _)| *id);
+			for (student_id, student) in students {
+				let Some(identity) = Pallet::<T>::identity_of(&student) else { continue };
+				reads.saturating_accrue(2);
+				if !StudentIdentities::<T>::contains_key(identity) {
+					StudentIdentities::<T>::insert(identity, student_id);
+					writes.saturating_inc();
+				}
+			}
+
+			// Both layouts of the owner lists share the storage prefix, so the old entries are
+			// drained before writing the new ones
+			let owners = OldStudentsByOwner::<T>::drain().collect::<Vec<_>>();
+			for (owner, student_ids) in owners {
+				reads.saturating_inc();
+				writes.saturating_accrue(2);
+				OwnedStudentCount::<T>::insert(&owner, student_ids.len() as u32);
+				for student_id in student_ids {
+					StudentsByOwner::<T>::insert(&owner, student_id, ());
+					StudentOwner::<T>::insert(student_id, &owner);
+					writes.saturating_accrue(2);
+				}
+			}
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+
+	/// [`InnerMigrateV0ToV1`] wrapped in a [`VersionedMigration`], so it only runs once.
+	pub type MigrateV0ToV1<T> = VersionedMigration<
+		0,
+		1,
+		InnerMigrateV0ToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
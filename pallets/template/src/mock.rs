@@ -1,8 +1,14 @@
+use alloc::collections::BTreeMap;
+use core::cell::RefCell;
 use frame::{
-	deps::{frame_support::weights::constants::RocksDbWeight, frame_system::GenesisConfig},
+	deps::frame_support::weights::constants::RocksDbWeight,
 	prelude::*,
 	runtime::prelude::*,
 	testing_prelude::*,
+	traits::{TryConvert, UnixTime},
+};
+use polkadot_sdk::{
+	pallet_balances, staging_xcm::latest::prelude::*, staging_xcm_executor::traits::ConvertLocation,
 };
 
 // Configure a mock runtime to test the pallet.
@@ -27,6 +33,84 @@ mod test_runtime {
 	pub type System = frame_system;
 	#[runtime::pallet_index(1)]
 	pub type Template = crate;
+	#[runtime::pallet_index(2)]
+	pub type XcmOrigin = xcm_origin;
+	#[runtime::pallet_index(3)]
+	pub type Balances = pallet_balances;
+}
+
+/// Stand-in for the `pallet_xcm` origins, carrying the location an XCM or a query response
+/// was sent from.
+#[frame::pallet]
+pub mod xcm_origin {
+	use frame::prelude::*;
+	use polkadot_sdk::staging_xcm::latest::Location;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::origin]
+	#[derive(
+		Clone, PartialEq, Eq, Debug, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen,
+	)]
+	pub enum Origin {
+		Xcm(Location),
+		Response(Location),
+	}
+}
+
+impl xcm_origin::Config for Test {}
+
+/// Dispatch origin of a call sent from `location` over XCM.
+pub fn xcm(location: Location) -> RuntimeOrigin {
+	xcm_origin::Origin::Xcm(location).into()
+}
+
+/// Dispatch origin of a query response from `location`.
+pub fn response(location: Location) -> RuntimeOrigin {
+	xcm_origin::Origin::Response(location).into()
+}
+
+/// Location of `account` on sibling parachain `para_id`.
+pub fn sibling_account(para_id: u32, account: u64) -> Location {
+	Location::new(1, [Parachain(para_id), AccountIndex64 { network: None, index: account }])
+}
+
+pub struct EnsureXcmOrigin;
+impl EnsureOrigin<RuntimeOrigin> for EnsureXcmOrigin {
+	type Success = Location;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<Location, RuntimeOrigin> {
+		match o.clone().into() {
+			Ok(xcm_origin::Origin::Xcm(location)) => Ok(location),
+			_ => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(xcm(Location::new(1, [Parachain(1000)])))
+	}
+}
+
+pub struct EnsureResponseOrigin;
+impl EnsureOrigin<RuntimeOrigin> for EnsureResponseOrigin {
+	type Success = Location;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<Location, RuntimeOrigin> {
+		match o.clone().into() {
+			Ok(xcm_origin::Origin::Response(location)) => Ok(location),
+			_ => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(response(Location::new(1, [Parachain(1000)])))
+	}
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
@@ -35,14 +119,244 @@ impl frame_system::Config for Test {
 	type Block = MockBlock<Test>;
 	type BlockHashCount = ConstU64<250>;
 	type DbWeight = RocksDbWeight;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+/// Owner and metadata of an item kept by `TestItems`.
+pub type Item = (u64, Vec<u8>);
+
+thread_local! {
+	pub static SENT_XCM: RefCell<Vec<(Location, Xcm<()>)>> = const { RefCell::new(Vec::new()) };
+	pub static NOW: RefCell<u64> = const { RefCell::new(MOCK_NOW) };
+	pub static QUERIES: RefCell<Vec<(Location, u64)>> = const { RefCell::new(Vec::new()) };
+	pub static COLLECTIONS: RefCell<Vec<u32>> = const { RefCell::new(Vec::new()) };
+	pub static ITEMS: RefCell<BTreeMap<(u32, u32), Item>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// 2025-06-15T00:00:00Z, the initial time of `MockTime`.
+pub const MOCK_NOW: u64 = 1_749_945_600;
+
+/// Unix time set through `set_now`, in seconds.
+pub struct MockTime;
+impl UnixTime for MockTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_secs(NOW.with(|n| *n.borrow()))
+	}
+}
+
+pub fn set_now(secs: u64) {
+	NOW.with(|n| *n.borrow_mut() = secs);
+}
+
+/// Messages sent through `TestXcmSender` so far.
+pub fn sent_xcm() -> Vec<(Location, Xcm<()>)> {
+	SENT_XCM.with(|q| q.borrow().clone())
+}
+
+/// Records outgoing messages instead of routing them.
+pub struct TestXcmSender;
+impl SendXcm for TestXcmSender {
+	type Ticket = (Location, Xcm<()>);
+
+	fn validate(
+		dest: &mut Option<Location>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let pair = (
+			dest.take().ok_or(SendError::MissingArgument)?,
+			msg.take().ok_or(SendError::MissingArgument)?,
+		);
+		Ok((pair, Assets::new()))
+	}
+
+	fn deliver(pair: Self::Ticket) -> Result<XcmHash, SendError> {
+		let hash = pair.1.using_encoded(frame::hashing::blake2_256);
+		SENT_XCM.with(|q| q.borrow_mut().push(pair));
+		Ok(hash)
+	}
+}
+
+/// Queries registered through `TestQueries` so far, by responder and timeout. The id of a
+/// query is its index.
+pub fn queries() -> Vec<(Location, u64)> {
+	QUERIES.with(|q| q.borrow().clone())
+}
+
+pub struct TestQueries;
+impl crate::NotifyQuery<RuntimeCall, u64> for TestQueries {
+	fn new_notify_query(responder: Location, _notify: RuntimeCall, timeout: u64) -> QueryId {
+		QUERIES.with(|q| {
+			let mut queries = q.borrow_mut();
+			queries.push((responder, timeout));
+			queries.len() as QueryId - 1
+		})
+	}
+}
+
+/// Collections created through `TestItems` so far.
+pub fn collections() -> Vec<u32> {
+	COLLECTIONS.with(|c| c.borrow().clone())
+}
+
+/// The item of a student, if `TestItems` has it.
+pub fn item(institution_id: u32, student_id: u32) -> Option<Item> {
+	ITEMS.with(|i| i.borrow().get(&(institution_id, student_id)).cloned())
+}
+
+/// Location of the collection of an institution in `TestItems`, as if in a pallet at index 9.
+pub fn collection_location(institution_id: u32) -> Location {
+	Location::new(0, [PalletInstance(9), GeneralIndex(institution_id.into())])
+}
+
+pub struct TestItems;
+impl crate::StudentItems<u64> for TestItems {
+	fn create_collection(institution_id: u32) -> DispatchResult {
+		COLLECTIONS.with(|c| c.borrow_mut().push(institution_id));
+		Ok(())
+	}
+
+	fn mint(institution_id: u32, student_id: u32, owner: &u64, metadata: &[u8]) -> DispatchResult {
+		ensure!(collections().contains(&institution_id), DispatchError::Unavailable);
+		ITEMS.with(|i| {
+			i.borrow_mut().insert((institution_id, student_id), (*owner, metadata.to_vec()))
+		});
+		Ok(())
+	}
+
+	fn set_metadata(institution_id: u32, student_id: u32, metadata: &[u8]) -> DispatchResult {
+		ITEMS.with(|i| {
+			let mut items = i.borrow_mut();
+			let item =
+				items.get_mut(&(institution_id, student_id)).ok_or(DispatchError::Unavailable)?;
+			item.1 = metadata.to_vec();
+			Ok(())
+		})
+	}
+
+	fn transfer(institution_id: u32, student_id: u32, to: &u64) -> DispatchResult {
+		ITEMS.with(|i| {
+			let mut items = i.borrow_mut();
+			let item =
+				items.get_mut(&(institution_id, student_id)).ok_or(DispatchError::Unavailable)?;
+			item.0 = *to;
+			Ok(())
+		})
+	}
+
+	fn burn(institution_id: u32, student_id: u32) -> DispatchResult {
+		ITEMS
+			.with(|i| i.borrow_mut().remove(&(institution_id, student_id)))
+			.map(|_| ())
+			.ok_or(DispatchError::Unavailable)
+	}
+
+	fn owner(institution_id: u32, student_id: u32) -> Option<u64> {
+		item(institution_id, student_id).map(|(owner, _)| owner)
+	}
+
+	fn asset(institution_id: u32, student_id: u32) -> Asset {
+		(collection_location(institution_id), Index(student_id.into())).into()
+	}
+}
+
+parameter_types! {
+	pub UniversalLocation: InteriorLocation = Parachain(2000).into();
+	pub const ScholarshipPalletId: PalletId = PalletId(*b"py/schlr");
+	pub const StipendSendWeight: Weight = Weight::from_parts(1_000_000, 0);
+}
+
+/// Test accounts are `u64`, so they are represented by an `AccountIndex64` junction.
+pub struct AccountIndexToLocation;
+impl<'a> TryConvert<&'a u64, Location> for AccountIndexToLocation {
+	fn try_convert(who: &'a u64) -> Result<Location, &'a u64> {
+		Ok(AccountIndex64 { network: None, index: *who }.into())
+	}
+}
+
+/// Local `AccountIndex64` locations map back onto test accounts, accounts on sibling parachains
+/// onto `para_id * 1000 + index` and sibling parachains themselves onto `para_id * 1000`.
+pub struct AccountIndexAliases;
+impl ConvertLocation<u64> for AccountIndexAliases {
+	fn convert_location(location: &Location) -> Option<u64> {
+		match location.unpack() {
+			(0, [AccountIndex64 { index, .. }]) => Some(*index),
+			(1, [Parachain(para_id)]) => Some(*para_id as u64 * 1000),
+			(1, [Parachain(para_id), AccountIndex64 { index, .. }]) =>
+				Some(*para_id as u64 * 1000 + index),
+			_ => None,
+		}
+	}
 }
 
 impl crate::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type MaxNameLen = ConstU32<10>;
+	type MaxSurnameLen = ConstU32<20>;
+	type MaxReferenceLen = ConstU32<32>;
+	type MaxAge = ConstU32<150>;
+	type MaxHistoryLen = ConstU32<4>;
+	type UnixTime = MockTime;
+	type XcmSender = TestXcmSender;
+	type XcmOrigin = EnsureXcmOrigin;
+	type AccountIdToLocation = AccountIndexToLocation;
+	type LocationToAccountId = AccountIndexAliases;
+	type RuntimeCall = RuntimeCall;
+	type GraduationDestinationPara = ConstU32<2000>;
+	type GraduationDestinationInstitution = ConstU32<0>;
+	type TransferApprovalPeriod = ConstU64<10>;
+	type DeletionGracePeriod = ConstU64<5>;
+	type MaxDeletionsPerBlock = ConstU32<2>;
+	type MaxStudentsPerOwner = ConstU32<3>;
+	type InstitutionRegistrar = EnsureRoot<u64>;
+	type MaxInstitutionNameLen = ConstU32<32>;
+	type MaxInstitutionAdmins = ConstU32<3>;
+	type MaxPositionLen = ConstU32<16>;
+	type MaxJobOffers = ConstU32<2>;
+	type MaxJobOffersPerPara = ConstU32<1>;
+	type Queries = TestQueries;
+	type ResponseOrigin = EnsureResponseOrigin;
+	type UniversalLocation = UniversalLocation;
+	type VerificationTimeout = ConstU64<20>;
+	type MaxPendingVerifications = ConstU32<2>;
+	type MaxVerificationsPerBlock = ConstU32<2>;
+	type Items = TestItems;
+	type Currency = Balances;
+	type PalletId = ScholarshipPalletId;
+	type StipendPeriod = ConstU64<10>;
+	type MaxStipendsPerBlock = ConstU32<2>;
+	type StipendSendWeight = StipendSendWeight;
+	type MaxInvoicesPerStudent = ConstU32<2>;
+	type MaxCommitteeMembers = ConstU32<3>;
+	type GraduationApprovalPeriod = ConstU64<10>;
 }
 
+/// Institution registered at genesis, administered by accounts 1 and 2.
+pub const UNIVERSITY: u32 = 0;
+
+/// Balance of the admins of [`UNIVERSITY`] at genesis.
+pub const ADMIN_BALANCE: u64 = 1_000;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> TestState {
-	GenesisConfig::<Test>::default().build_storage().unwrap().into()
-}
\ No newline at end of file
+	let mut ext: TestState = RuntimeGenesisConfig {
+		template: crate::GenesisConfig {
+			institutions: vec![(b"Polkadot University".to_vec(), vec![1, 2])],
+		},
+		balances: pallet_balances::GenesisConfig {
+			balances: vec![(1, ADMIN_BALANCE), (2, ADMIN_BALANCE)],
+			..Default::default()
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap()
+	.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,22 @@
+//! Runtime API of the student registry.
+
+use crate::{AlumniRecord, HistoryEntry};
+use alloc::vec::Vec;
+use codec::Codec;
+
+frame::deps::sp_api::decl_runtime_apis! {
+	/// Queries about the students of this chain.
+	pub trait StudentRegistryApi<AccountId, BlockNumber, Hash>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+	{
+		/// Alumni record of a student that graduated from this chain to another one.
+		fn alumnus(student_id: u32) -> Option<AlumniRecord<AccountId, BlockNumber, Hash>>;
+
+		/// Recorded changes of a student, oldest first, including those made on the chains it
+		/// was received from.
+		fn student_timeline(student_id: u32) -> Vec<HistoryEntry<Hash>>;
+	}
+}
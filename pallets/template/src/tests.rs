@@ -1,20 +1,2205 @@
-use crate::{mock::*, Error, Something};
+use crate::{
+	mock::*, Accreditation, Alumni, AlumniRecord, Beneficiary, Change, ContractType, Date,
+	DeletedStudents, Error, Event, Gender, GraduationDestination, HistoryEntry, NextPurgeBlock,
+	Pallet, PendingTransfers, PendingVerificationCount, PendingVerifications, PersonalData,
+	PersonalDataFields, PersonalDataInput, PurgeAgenda, ReceivedIndex, StipendAgenda, Stipends,
+	Student, StudentInstitution, StudentStatus, Students, StudentsByOwner, Verification,
+};
 use frame::testing_prelude::*;
+use polkadot_sdk::staging_xcm::latest::prelude::*;
+
+fn create(owner: u64) -> u32 {
+	let id = crate::StudentCount::<Test>::get();
+	assert_ok!(Template::create_student(RuntimeOrigin::signed(owner), UNIVERSITY, ada()));
+	id
+}
+
+/// Create a student other than the one of [`create`].
+fn create_another(owner: u64) -> u32 {
+	let id = crate::StudentCount::<Test>::get();
+	let input = PersonalDataInput { given_name: b"Augusta".to_vec(), ..ada() };
+	assert_ok!(Template::create_student(RuntimeOrigin::signed(owner), UNIVERSITY, input));
+	id
+}
+
+/// Have para 1000 announce that its account 7 sends student `source_id`, as it does when
+/// graduating a student.
+fn announce(source_id: u32) {
+	assert_ok!(Template::expect_student(
+		xcm(Location::new(1, [Parachain(1000)])),
+		source_id,
+		AccountIndex64 { network: None, index: 7 }.into()
+	));
+}
+
+fn ada() -> PersonalDataInput<H256> {
+	PersonalDataInput {
+		given_name: b"Ada".to_vec(),
+		middle_name: vec![],
+		family_name: b"Lovelace".to_vec(),
+		date_of_birth: Date { year: 2004, month: 6, day: 16 },
+		gender: Gender::Female,
+		national_id_hash: None,
+		email_hash: None,
+	}
+}
+
+fn personal() -> PersonalData<Test> {
+	Pallet::<Test>::validate_personal_data(ada()).unwrap()
+}
+
+fn student() -> Student<Test> {
+	Student {
+		personal: Some(personal()),
+		status: StudentStatus::Transferred,
+		status_since: MOCK_NOW,
+		commitment: None,
+	}
+}
+
+#[test]
+fn graduate_student_descends_into_owner_account() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+
+		let sent = sent_xcm();
+		assert_eq!(sent.len(), 1);
+		let (dest, message) = &sent[0];
+		assert_eq!(*dest, Location::new(1, [Parachain(2000)]));
+		assert!(matches!(
+			message.0.as_slice(),
+			[
+				UnpaidExecution { .. },
+				ReserveAssetDeposited(_),
+				Transact { origin_kind: OriginKind::Xcm, call: announcement, .. },
+				DescendOrigin(interior),
+				Transact { origin_kind: OriginKind::Xcm, .. },
+				DepositAsset { .. },
+			] if interior.as_slice() == [AccountIndex64 { network: None, index: 1 }] &&
+				announcement.clone().into_encoded() == RuntimeCall::Template(
+					crate::Call::expect_student { source_id: id, sender: interior.clone() }
+				).encode()
+		));
+		assert!(!Students::<Test>::contains_key(id));
+	});
+}
+
+#[test]
+fn graduate_student_requires_owner() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_noop!(
+			Template::graduate_student(RuntimeOrigin::signed(2), id, None),
+			Error::<Test>::NotStudentOwner
+		);
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn receive_student_assigns_owner_from_origin() {
+	new_test_ext().execute_with(|| {
+		announce(3);
+		assert_ok!(Template::receive_student(
+			xcm(sibling_account(1000, 7)),
+			student(),
+			3,
+			None,
+			Default::default(),
+			UNIVERSITY
+		));
+
+		assert_eq!(Students::<Test>::get(0), Some(student()));
+		assert_eq!(Pallet::<Test>::students_of(1_000_007, None, 10), vec![0]);
+		assert_eq!(ReceivedIndex::<Test>::get(1000, 3), Some(0));
+		System::assert_last_event(
+			Event::StudentReceived { student_id: 0, owner: 1_000_007 }.into(),
+		);
+	});
+}
+
+#[test]
+fn only_announced_students_are_received() {
+	new_test_ext().execute_with(|| {
+		let receive = |account| {
+			Template::receive_student(
+				xcm(sibling_account(1000, account)),
+				student(),
+				3,
+				None,
+				Default::default(),
+				UNIVERSITY,
+			)
+		};
+		assert_noop!(receive(7), Error::<Test>::StudentNotAnnounced);
+
+		// Accounts on the sending chain cannot announce students
+		assert_noop!(
+			Template::expect_student(
+				xcm(sibling_account(1000, 8)),
+				3,
+				AccountIndex64 { network: None, index: 8 }.into()
+			),
+			Error::<Test>::NotFromSiblingChain
+		);
+
+		// Nor take the id of a student sent by another account
+		announce(3);
+		assert_noop!(receive(8), Error::<Test>::StudentNotAnnounced);
+		assert_ok!(receive(7));
+		assert_eq!(ReceivedIndex::<Test>::get(1000, 3), Some(0));
+	});
+}
+
+#[test]
+fn receive_student_rejects_non_xcm_origins() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Template::receive_student(
+				RuntimeOrigin::signed(7),
+				student(),
+				0,
+				None,
+				Default::default(),
+				UNIVERSITY
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Template::receive_student(
+				RuntimeOrigin::root(),
+				student(),
+				0,
+				None,
+				Default::default(),
+				UNIVERSITY
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn graduate_student_forwards_beneficiary() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		let beneficiary: Location = AccountIndex64 { network: None, index: 9 }.into();
+		assert_ok!(Template::graduate_student(
+			RuntimeOrigin::signed(1),
+			id,
+			Some(Beneficiary::Location(beneficiary.clone())),
+		));
+
+		System::assert_last_event(
+			Event::StudentGraduatedAndTransferred {
+				who: 1,
+				student_id: id,
+				destination: Location::new(1, [Parachain(2000)]),
+				beneficiary: Some(beneficiary),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn graduate_student_rejects_unconvertible_beneficiary() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_noop!(
+			Template::graduate_student(
+				RuntimeOrigin::signed(1),
+				id,
+				Some(Beneficiary::AccountKey20([7; 20])),
+			),
+			Error::<Test>::BeneficiaryNotConvertible
+		);
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn receive_student_assigns_beneficiary() {
+	new_test_ext().execute_with(|| {
+		let beneficiary: Location = AccountIndex64 { network: None, index: 9 }.into();
+		announce(0);
+		assert_ok!(Template::receive_student(
+			xcm(sibling_account(1000, 7)),
+			student(),
+			0,
+			Some(beneficiary),
+			Default::default(),
+			UNIVERSITY,
+		));
+
+		assert!(Pallet::<Test>::students_of(1_000_007, None, 10).is_empty());
+		assert_eq!(Pallet::<Test>::students_of(9, None, 10), vec![0]);
+	});
+}
+
+#[test]
+fn committed_student_stores_no_plaintext() {
+	new_test_ext().execute_with(|| {
+		let hash = Pallet::<Test>::commitment_of(&personal(), b"salt");
+		assert_ok!(Template::create_committed_student(
+			RuntimeOrigin::signed(1),
+			UNIVERSITY,
+			hash,
+			b"ipfs://record".to_vec(),
+		));
+
+		let stored = Students::<Test>::get(0).unwrap();
+		assert_eq!(stored.personal, None);
+		assert_eq!(stored.commitment.map(|c| c.hash), Some(hash));
+	});
+}
+
+#[test]
+fn verify_student_data_checks_commitment() {
+	new_test_ext().execute_with(|| {
+		let hash = Pallet::<Test>::commitment_of(&personal(), b"salt");
+		assert_ok!(Template::create_committed_student(
+			RuntimeOrigin::signed(1),
+			UNIVERSITY,
+			hash,
+			vec![]
+		));
+
+		assert_ok!(Template::verify_student_data(
+			RuntimeOrigin::signed(2),
+			0,
+			ada(),
+			b"salt".to_vec(),
+		));
+		System::assert_last_event(Event::StudentDataVerified { who: 2, student_id: 0 }.into());
+
+		assert_noop!(
+			Template::verify_student_data(RuntimeOrigin::signed(2), 0, ada(), b"pepper".to_vec(),),
+			Error::<Test>::CommitmentMismatch
+		);
+	});
+}
+
+#[test]
+fn verify_student_data_requires_commitment() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_noop!(
+			Template::verify_student_data(RuntimeOrigin::signed(2), id, ada(), vec![],),
+			Error::<Test>::NoCommitment
+		);
+	});
+}
+
+#[test]
+fn erase_student_data_wipes_local_record() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_noop!(
+			Template::erase_student_data(RuntimeOrigin::signed(2), id),
+			Error::<Test>::NotStudentOwner
+		);
+		assert_ok!(Template::erase_student_data(RuntimeOrigin::signed(1), id));
+
+		assert_eq!(Students::<Test>::get(id).unwrap().personal, None);
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn erase_student_data_propagates_to_destination() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+		let alumnus = Alumni::<Test>::get(id).unwrap();
+		assert_eq!((alumnus.destination_para, alumnus.graduated_by), (2000, 1));
+
+		assert_noop!(
+			Template::erase_student_data(RuntimeOrigin::signed(2), id),
+			Error::<Test>::NotStudentOwner
+		);
+		assert_ok!(Template::erase_student_data(RuntimeOrigin::signed(1), id));
+
+		let sent = sent_xcm();
+		assert_eq!(sent.len(), 2);
+		assert_eq!(sent[1].0, Location::new(1, [Parachain(2000)]));
+		assert!(matches!(
+			sent[1].1 .0.as_slice(),
+			[UnpaidExecution { .. }, Transact { origin_kind: OriginKind::Xcm, .. }]
+		));
+	});
+}
+
+#[test]
+fn receive_erasure_wipes_copy_from_sender() {
+	new_test_ext().execute_with(|| {
+		announce(3);
+		assert_ok!(Template::receive_student(
+			xcm(sibling_account(1000, 7)),
+			student(),
+			3,
+			None,
+			Default::default(),
+			UNIVERSITY
+		));
+
+		// Only the chain the student came from can erase it
+		assert_noop!(
+			Template::receive_erasure(xcm(Location::new(1, [Parachain(1001)])), 3),
+			Error::<Test>::StudentNotFound
+		);
+		// Nor can accounts on that chain
+		assert_noop!(
+			Template::receive_erasure(xcm(sibling_account(1000, 7)), 3),
+			Error::<Test>::NotFromSiblingChain
+		);
+		assert_ok!(Template::receive_erasure(xcm(Location::new(1, [Parachain(1000)])), 3));
+
+		let stored = Students::<Test>::get(0).unwrap();
+		assert_eq!(stored.personal, None);
+		assert_eq!(stored.status, StudentStatus::Transferred);
+		System::assert_last_event(Event::StudentDataErased { student_id: 0 }.into());
+	});
+}
+
+#[test]
+fn migrate_v0_to_v1_converts_students() {
+	use crate::{
+		migrations::v1::{MigrateV0ToV1, OldStudent},
+		StudentIdentities,
+	};
+	use frame::deps::frame_support::{storage::unhashed, traits::OnRuntimeUpgrade};
+
+	new_test_ext().execute_with(|| {
+		let old = |has_graduated| OldStudent::<Test> {
+			name: b"Ada".to_vec().try_into().unwrap(),
+			surname: b"Lovelace".to_vec().try_into().unwrap(),
+			age: 21,
+			gender: Gender::Female,
+			has_graduated,
+		};
+		unhashed::put(&Students::<Test>::hashed_key_for(0), &old(false));
+		unhashed::put(&Students::<Test>::hashed_key_for(1), &old(true));
+		StorageVersion::new(0).put::<Template>();
+		set_now(MOCK_NOW + 60);
+
+		MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		let migrated = Students::<Test>::get(0).unwrap();
+		let personal = migrated.personal.clone().unwrap();
+		assert_eq!(personal.given_name.into_inner(), b"Ada".to_vec());
+		assert_eq!(personal.date_of_birth, Date { year: 2004, month: 1, day: 1 });
+		assert_eq!(personal.date_of_birth.years_until(&Pallet::<Test>::today()), Some(21));
+		assert_eq!(migrated.commitment, None);
+
+		let status = |id| Students::<Test>::get(id).map(|s| (s.status, s.status_since));
+		assert_eq!(status(0), Some((StudentStatus::Enrolled, MOCK_NOW + 60)));
+		assert_eq!(status(1), Some((StudentStatus::Graduated, MOCK_NOW + 60)));
+
+		// Of the two records of the same person only the lowest id is indexed
+		let identity = Pallet::<Test>::identity_of(&migrated).unwrap();
+		assert_eq!(StudentIdentities::<Test>::get(identity), Some(0));
+		assert_eq!(StorageVersion::get::<Template>(), 1);
+	});
+}
+
+#[test]
+fn migrate_v0_to_v1_splits_owner_lists() {
+	use crate::{
+		migrations::v1::{MigrateV0ToV1, OldStudentsByOwner},
+		OwnedStudentCount, StudentOwner,
+	};
+	use frame::deps::frame_support::traits::OnRuntimeUpgrade;
+
+	new_test_ext().execute_with(|| {
+		OldStudentsByOwner::<Test>::insert(1, BoundedVec::truncate_from(vec![0, 2]));
+		OldStudentsByOwner::<Test>::insert(5, BoundedVec::truncate_from(vec![1]));
+		StorageVersion::new(0).put::<Template>();
+
+		MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		assert!(StudentsByOwner::<Test>::contains_key(1, 0));
+		assert!(StudentsByOwner::<Test>::contains_key(1, 2));
+		assert!(StudentsByOwner::<Test>::contains_key(5, 1));
+		assert_eq!(StudentsByOwner::<Test>::iter().count(), 3);
+		assert_eq!((OwnedStudentCount::<Test>::get(1), OwnedStudentCount::<Test>::get(5)), (2, 1));
+		assert_eq!(StudentOwner::<Test>::get(2), Some(1));
+		assert_eq!(StorageVersion::get::<Template>(), 1);
+	});
+}
+
+#[test]
+fn create_student_validates_personal_data() {
+	new_test_ext().execute_with(|| {
+		let origin = || RuntimeOrigin::signed(1);
+		let with = |f: fn(&mut PersonalDataInput<H256>)| {
+			let mut data = ada();
+			f(&mut data);
+			data
+		};
+
+		assert_noop!(
+			Template::create_student(origin(), UNIVERSITY, with(|d| d.given_name.clear())),
+			Error::<Test>::EmptyName
+		);
+		assert_noop!(
+			Template::create_student(origin(), UNIVERSITY, with(|d| d.family_name.clear())),
+			Error::<Test>::EmptySurname
+		);
+		assert_noop!(
+			Template::create_student(origin(), UNIVERSITY, with(|d| d.middle_name = vec![0xff])),
+			Error::<Test>::NameNotUtf8
+		);
+		assert_noop!(
+			Template::create_student(origin(), UNIVERSITY, with(|d| d.family_name = vec![0xc3])),
+			Error::<Test>::SurnameNotUtf8
+		);
+		assert_noop!(
+			Template::create_student(origin(), UNIVERSITY, with(|d| d.given_name = vec![b'a'; 11])),
+			Error::<Test>::NameTooLong
+		);
+		assert_noop!(
+			Template::create_student(
+				origin(),
+				UNIVERSITY,
+				with(|d| d.date_of_birth = Date { year: 2001, month: 2, day: 29 })
+			),
+			Error::<Test>::InvalidDateOfBirth
+		);
+		assert_noop!(
+			Template::create_student(
+				origin(),
+				UNIVERSITY,
+				with(|d| d.date_of_birth = Date { year: 2025, month: 6, day: 16 })
+			),
+			Error::<Test>::DateOfBirthInFuture
+		);
+		assert_noop!(
+			Template::create_student(
+				origin(),
+				UNIVERSITY,
+				with(|d| d.date_of_birth = Date { year: 1874, month: 6, day: 15 })
+			),
+			Error::<Test>::DateOfBirthTooOld
+		);
+
+		// Leap days exist in leap years
+		assert_ok!(Template::create_student(
+			origin(),
+			UNIVERSITY,
+			with(|d| d.date_of_birth = Date { year: 2004, month: 2, day: 29 })
+		));
+	});
+}
+
+#[test]
+fn student_age_follows_the_clock() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_eq!(Pallet::<Test>::student_age(id), Some(20));
+
+		// A day later is the 21st birthday
+		set_now(MOCK_NOW + 86_400);
+		assert_eq!(Pallet::<Test>::student_age(id), Some(21));
+
+		assert_ok!(Template::erase_student_data(RuntimeOrigin::signed(1), id));
+		assert_eq!(Pallet::<Test>::student_age(id), None);
+	});
+}
+
+#[test]
+fn linked_student_must_approve_graduation() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::set_student_account(RuntimeOrigin::signed(1), id, Some(5)));
+
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+		assert!(sent_xcm().is_empty());
+		assert!(PendingTransfers::<Test>::contains_key(id));
+		System::assert_last_event(
+			Event::TransferRequested { student_id: id, student_account: 5, expires_at: 11 }.into(),
+		);
+		assert_noop!(
+			Template::graduate_student(RuntimeOrigin::signed(1), id, None),
+			Error::<Test>::TransferAlreadyPending
+		);
+
+		assert_noop!(
+			Template::approve_transfer(RuntimeOrigin::signed(1), id),
+			Error::<Test>::NotStudentAccount
+		);
+		assert_ok!(Template::approve_transfer(RuntimeOrigin::signed(5), id));
+
+		assert_eq!(sent_xcm().len(), 1);
+		assert!(!Students::<Test>::contains_key(id));
+		assert!(!PendingTransfers::<Test>::contains_key(id));
+	});
+}
+
+#[test]
+fn only_the_student_changes_a_linked_account() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_noop!(
+			Template::set_student_account(RuntimeOrigin::signed(2), id, Some(5)),
+			Error::<Test>::NotStudentOwner
+		);
+		assert_ok!(Template::set_student_account(RuntimeOrigin::signed(1), id, Some(5)));
+
+		// The owner can no longer unlink the student to graduate it alone
+		for account in [None, Some(1)] {
+			assert_noop!(
+				Template::set_student_account(RuntimeOrigin::signed(1), id, account),
+				Error::<Test>::NotStudentAccount
+			);
+		}
+		assert_ok!(Template::set_student_account(RuntimeOrigin::signed(5), id, Some(6)));
+		assert_ok!(Template::set_student_account(RuntimeOrigin::signed(6), id, None));
+		assert_ok!(Template::set_student_account(RuntimeOrigin::signed(1), id, Some(5)));
+	});
+}
+
+#[test]
+fn transfer_requests_expire() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::set_student_account(RuntimeOrigin::signed(1), id, Some(5)));
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+
+		System::set_block_number(12);
+		assert_noop!(
+			Template::approve_transfer(RuntimeOrigin::signed(5), id),
+			Error::<Test>::TransferExpired
+		);
+
+		// An expired request can be replaced by a new one
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+		assert_ok!(Template::approve_transfer(RuntimeOrigin::signed(5), id));
+		assert_eq!(sent_xcm().len(), 1);
+	});
+}
+
+#[test]
+fn approve_transfer_requires_pending_request() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::set_student_account(RuntimeOrigin::signed(1), id, Some(5)));
+		assert_noop!(
+			Template::approve_transfer(RuntimeOrigin::signed(5), id),
+			Error::<Test>::NoPendingTransfer
+		);
+	});
+}
+
+#[test]
+fn duplicate_students_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_noop!(
+			Template::create_student(RuntimeOrigin::signed(2), UNIVERSITY, ada()),
+			Error::<Test>::StudentAlreadyExists
+		);
+
+		// A national id identifies the person regardless of the name
+		let mut with_id = ada();
+		with_id.national_id_hash = Some(H256::repeat_byte(7));
+		assert_ok!(Template::create_student(RuntimeOrigin::signed(1), UNIVERSITY, with_id.clone()));
+		with_id.given_name = b"Augusta".to_vec();
+		assert_noop!(
+			Template::create_student(RuntimeOrigin::signed(1), UNIVERSITY, with_id.clone()),
+			Error::<Test>::StudentAlreadyExists
+		);
+		assert_noop!(
+			Template::update_student(RuntimeOrigin::signed(1), id, with_id),
+			Error::<Test>::StudentAlreadyExists
+		);
+
+		// Purging or erasing a record frees its identity
+		assert_ok!(Template::delete_student(RuntimeOrigin::signed(1), id));
+		assert_noop!(
+			Template::create_student(RuntimeOrigin::signed(2), UNIVERSITY, ada()),
+			Error::<Test>::StudentAlreadyExists
+		);
+		System::set_block_number(6);
+		Template::on_idle(6, Weight::MAX);
+		let id = create(1);
+		assert_ok!(Template::erase_student_data(RuntimeOrigin::signed(1), id));
+		create(1);
+	});
+}
+
+#[test]
+fn unindexed_duplicates_leave_the_original_indexed() {
+	new_test_ext().execute_with(|| {
+		let original = create(1);
+		let identity = Pallet::<Test>::identity_of(&Students::<Test>::get(original).unwrap());
+
+		// A duplicate left unindexed by the storage migration
+		let duplicate = create_another(1);
+		let other = Pallet::<Test>::identity_of(&Students::<Test>::get(duplicate).unwrap());
+		crate::StudentIdentities::<Test>::remove(other.unwrap());
+		Students::<Test>::insert(duplicate, Students::<Test>::get(original).unwrap());
+
+		assert_ok!(Template::erase_student_data(RuntimeOrigin::signed(1), duplicate));
+		assert_eq!(crate::StudentIdentities::<Test>::get(identity.unwrap()), Some(original));
+		assert_noop!(
+			Template::create_student(RuntimeOrigin::signed(2), UNIVERSITY, ada()),
+			Error::<Test>::StudentAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn duplicate_received_students_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let sender = || xcm(sibling_account(1000, 7));
+		announce(3);
+		assert_ok!(Template::receive_student(
+			sender(),
+			student(),
+			3,
+			None,
+			Default::default(),
+			UNIVERSITY
+		));
+
+		// The same record sent twice
+		let mut other = student();
+		other.personal.as_mut().unwrap().given_name = b"Grace".to_vec().try_into().unwrap();
+		announce(3);
+		assert_noop!(
+			Template::receive_student(
+				sender(),
+				other.clone(),
+				3,
+				None,
+				Default::default(),
+				UNIVERSITY
+			),
+			Error::<Test>::StudentAlreadyExists
+		);
+		// The same person under another source id, or already registered locally
+		announce(4);
+		assert_noop!(
+			Template::receive_student(sender(), student(), 4, None, Default::default(), UNIVERSITY),
+			Error::<Test>::StudentAlreadyExists
+		);
+		assert_noop!(
+			Template::create_student(RuntimeOrigin::signed(1), UNIVERSITY, ada()),
+			Error::<Test>::StudentAlreadyExists
+		);
+		announce(4);
+		assert_ok!(Template::receive_student(
+			sender(),
+			other,
+			4,
+			None,
+			Default::default(),
+			UNIVERSITY
+		));
+	});
+}
+
+#[test]
+fn student_ids_do_not_wrap() {
+	new_test_ext().execute_with(|| {
+		crate::StudentCount::<Test>::put(u32::MAX);
+		assert_noop!(
+			Template::create_student(RuntimeOrigin::signed(1), UNIVERSITY, ada()),
+			Error::<Test>::StorageOverflow
+		);
+		announce(3);
+		assert_noop!(
+			Template::receive_student(
+				xcm(sibling_account(1000, 7)),
+				student(),
+				3,
+				None,
+				Default::default(),
+				UNIVERSITY
+			),
+			Error::<Test>::StorageOverflow
+		);
+	});
+}
+
+#[test]
+fn status_changes_follow_the_lifecycle() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		let status = || Students::<Test>::get(id).map(|s| (s.status, s.status_since)).unwrap();
+		assert_eq!(status(), (StudentStatus::Enrolled, MOCK_NOW));
+
+		assert_noop!(
+			Template::suspend_student(RuntimeOrigin::signed(2), id),
+			Error::<Test>::NotStudentOwner
+		);
+		set_now(MOCK_NOW + 100);
+		assert_ok!(Template::suspend_student(RuntimeOrigin::signed(1), id));
+		assert_eq!(status(), (StudentStatus::Suspended, MOCK_NOW + 100));
+		System::assert_last_event(
+			Event::StudentStatusChanged {
+				student_id: id,
+				from: StudentStatus::Enrolled,
+				to: StudentStatus::Suspended,
+				at: MOCK_NOW + 100,
+			}
+			.into(),
+		);
+
+		// Suspended students can neither go on leave nor graduate
+		assert_noop!(
+			Template::grant_leave(RuntimeOrigin::signed(1), id),
+			Error::<Test>::InvalidStatusTransition
+		);
+		assert_noop!(
+			Template::graduate_student(RuntimeOrigin::signed(1), id, None),
+			Error::<Test>::InvalidStatusTransition
+		);
+		assert_noop!(
+			Template::set_employed(RuntimeOrigin::signed(1), id, true),
+			Error::<Test>::InvalidStatusTransition
+		);
+
+		assert_ok!(Template::withdraw_student(RuntimeOrigin::signed(1), id));
+		assert_ok!(Template::reinstate_student(RuntimeOrigin::signed(1), id));
+		assert_ok!(Template::grant_leave(RuntimeOrigin::signed(1), id));
+		assert_ok!(Template::reinstate_student(RuntimeOrigin::signed(1), id));
+		assert_eq!(status().0, StudentStatus::Enrolled);
+	});
+}
+
+#[test]
+fn graduation_sends_transferred_record() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+
+		let changes = System::events()
+			.into_iter()
+			.filter_map(|record| match record.event {
+				RuntimeEvent::Template(Event::StudentStatusChanged { from, to, .. }) =>
+					Some((from, to)),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(
+			changes,
+			vec![
+				(StudentStatus::Enrolled, StudentStatus::Graduated),
+				(StudentStatus::Graduated, StudentStatus::Transferred),
+			]
+		);
+
+		// The destination can mark the graduate as employed
+		announce(3);
+		assert_ok!(Template::receive_student(
+			xcm(sibling_account(1000, 7)),
+			student(),
+			3,
+			None,
+			Default::default(),
+			UNIVERSITY
+		));
+		let id = crate::StudentCount::<Test>::get() - 1;
+		assert_noop!(
+			Template::set_employed(RuntimeOrigin::signed(1_000_007), id, false),
+			Error::<Test>::InvalidStatusTransition
+		);
+		assert_ok!(Template::set_employed(RuntimeOrigin::signed(1_000_007), id, true));
+		assert_ok!(Template::set_employed(RuntimeOrigin::signed(1_000_007), id, false));
+		assert_eq!(Students::<Test>::get(id).unwrap().status, StudentStatus::Graduated);
+	});
+}
+
+#[test]
+fn graduation_leaves_alumni_record() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		System::set_block_number(5);
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+
+		// The diploma hash refers to the record as sent to the destination
+		assert_eq!(
+			Pallet::<Test>::alumnus(id),
+			Some(AlumniRecord {
+				destination_para: 2000,
+				institution_id: Some(UNIVERSITY),
+				graduated_by: 1,
+				graduated_at: 5,
+				diploma_hash: Pallet::<Test>::diploma_hash(&student()),
+			})
+		);
+		assert_eq!(Pallet::<Test>::alumnus(id + 1), None);
+	});
+}
+
+#[test]
+fn update_student_records_history() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		let before = Students::<Test>::get(id).unwrap();
+
+		let mut data = ada();
+		data.middle_name = b"King".to_vec();
+		set_now(MOCK_NOW + 10);
+		assert_ok!(Template::update_student(RuntimeOrigin::signed(1), id, data.clone()));
+
+		let new = Pallet::<Test>::validate_personal_data(data).unwrap();
+		System::assert_last_event(
+			Event::StudentUpdated { who: 1, student_id: id, old: Some(personal()), new }.into(),
+		);
+		assert_eq!(
+			Pallet::<Test>::student_timeline(id),
+			vec![
+				HistoryEntry { at: MOCK_NOW, change: Change::Created, previous: None },
+				HistoryEntry {
+					at: MOCK_NOW + 10,
+					change: Change::Updated,
+					previous: Some(BlakeTwo256::hash_of(&before)),
+				},
+			]
+		);
+	});
+}
+
+#[test]
+fn history_keeps_latest_changes() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		for _ in 0..2 {
+			assert_ok!(Template::suspend_student(RuntimeOrigin::signed(1), id));
+			assert_ok!(Template::reinstate_student(RuntimeOrigin::signed(1), id));
+		}
+
+		let changes = Pallet::<Test>::student_timeline(id)
+			.into_iter()
+			.map(|entry| entry.change)
+			.collect::<Vec<_>>();
+		assert_eq!(changes.len(), 4);
+		assert_eq!(
+			changes[0],
+			Change::StatusChanged { from: StudentStatus::Enrolled, to: StudentStatus::Suspended }
+		);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn timeline_follows_student_across_chains() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(Template::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(Something::<Test>::get().map(|v| v.block_number), Some(42));
+		let id = create(1);
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+		assert!(Pallet::<Test>::student_timeline(id).is_empty());
+
+		// The history travels with the record
+		let (_, message) = &sent_xcm()[0];
+		let Some(Transact { call, .. }) = message.0.iter().rfind(|i| matches!(i, Transact { .. }))
+		else {
+			panic!("no transact")
+		};
+		let RuntimeCall::Template(crate::Call::receive_student { history, .. }) =
+			RuntimeCall::decode(&mut &call.clone().into_encoded()[..]).unwrap()
+		else {
+			panic!("not a student transfer")
+		};
+		let changes = history.iter().map(|entry| entry.change.clone()).collect::<Vec<_>>();
+		assert_eq!(
+			changes,
+			vec![
+				Change::Created,
+				Change::StatusChanged {
+					from: StudentStatus::Enrolled,
+					to: StudentStatus::Graduated
+				},
+				Change::StatusChanged {
+					from: StudentStatus::Graduated,
+					to: StudentStatus::Transferred,
+				},
+				Change::Sent { destination_para: 2000 },
+			]
+		);
+
+		// The receiving chain appends the hop, dropping the oldest entry
+		announce(id);
+		assert_ok!(Template::receive_student(
+			xcm(sibling_account(1000, 7)),
+			student(),
+			id,
+			None,
+			history,
+			UNIVERSITY,
+		));
+		let received = crate::StudentCount::<Test>::get() - 1;
+		let timeline = Pallet::<Test>::student_timeline(received);
+		assert_eq!(timeline.len(), 4);
+		assert_eq!(timeline[2].change, Change::Sent { destination_para: 2000 });
+		assert_eq!(timeline[3].change, Change::Received { source_para: 1000, source_id: id });
+	});
+}
+
+#[test]
+fn deleted_student_can_be_restored() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::delete_student(RuntimeOrigin::signed(1), id));
+		System::assert_last_event(
+			Event::StudentDeleted { who: 1, student_id: id, purge_at: 6 }.into(),
+		);
+		assert!(!Students::<Test>::contains_key(id));
+		assert!(Pallet::<Test>::students_of(1, None, 10).is_empty());
+
+		assert_noop!(
+			Template::restore_student(RuntimeOrigin::signed(3), id),
+			Error::<Test>::NotStudentOwner
+		);
+		System::set_block_number(5);
+		assert_ok!(Template::restore_student(RuntimeOrigin::signed(1), id));
+
+		System::assert_last_event(Event::StudentRestored { student_id: id }.into());
+		assert_eq!(Students::<Test>::get(id).unwrap().personal, Some(personal()));
+		assert_eq!(Pallet::<Test>::students_of(1, None, 10), vec![id]);
+		assert!(!DeletedStudents::<Test>::contains_key(id));
+		assert!(PurgeAgenda::<Test>::get(6).is_empty());
+		let changes = Pallet::<Test>::student_timeline(id).into_iter().map(|entry| entry.change);
+		assert_eq!(
+			changes.collect::<Vec<_>>(),
+			vec![Change::Created, Change::Deleted, Change::Restored]
+		);
+	});
+}
+
+#[test]
+fn admin_deleted_student_can_be_restored_by_root() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::delete_any_student(RuntimeOrigin::root(), id));
+		System::assert_last_event(
+			Event::StudentDeletedByAdmin { student_id: id, purge_at: 6 }.into(),
+		);
+
+		assert_ok!(Template::restore_student(RuntimeOrigin::root(), id));
+		assert_eq!(Pallet::<Test>::students_of(1, None, 10), vec![id]);
+		assert_noop!(
+			Template::restore_student(RuntimeOrigin::root(), id),
+			Error::<Test>::StudentNotDeleted
+		);
+	});
+}
+
+#[test]
+fn expired_deletions_are_purged_on_idle() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::delete_student(RuntimeOrigin::signed(1), id));
+
+		// Nothing is due yet
+		Template::on_idle(5, Weight::MAX);
+		assert!(DeletedStudents::<Test>::contains_key(id));
+
+		System::set_block_number(6);
+		assert_noop!(
+			Template::restore_student(RuntimeOrigin::signed(1), id),
+			Error::<Test>::RestoreWindowExpired
+		);
+		Template::on_idle(6, Weight::MAX);
+
+		System::assert_last_event(Event::StudentPurged { student_id: id }.into());
+		assert!(!DeletedStudents::<Test>::contains_key(id));
+		assert!(Pallet::<Test>::student_timeline(id).is_empty());
+		assert_eq!(NextPurgeBlock::<Test>::get(), 7);
+		assert_noop!(
+			Template::restore_student(RuntimeOrigin::signed(1), id),
+			Error::<Test>::StudentNotDeleted
+		);
+	});
+}
+
+#[test]
+fn purging_is_bounded_by_idle_weight() {
+	new_test_ext().execute_with(|| {
+		let first = create(1);
+		let mut second_data = ada();
+		second_data.given_name = b"Grace".to_vec();
+		assert_ok!(Template::create_student(
+			RuntimeOrigin::signed(1),
+			UNIVERSITY,
+			second_data.clone()
+		));
+		let second = first + 1;
+		assert_ok!(Template::delete_student(RuntimeOrigin::signed(1), first));
+		assert_ok!(Template::delete_student(RuntimeOrigin::signed(1), second));
+
+		// The agenda of a block is bounded
+		second_data.given_name = b"Mary".to_vec();
+		assert_ok!(Template::create_student(RuntimeOrigin::signed(1), UNIVERSITY, second_data));
+		assert_noop!(
+			Template::delete_student(RuntimeOrigin::signed(1), second + 1),
+			Error::<Test>::TooManyDeletions
+		);
+
+		// Enough weight for the cursor, one agenda and a single purge
+		let db = <Test as frame_system::Config>::DbWeight::get();
+		let one = db.reads_writes(2, 2).saturating_add(db.reads_writes(7, 24));
+		assert_eq!(Template::on_idle(6, one), one);
+		assert!(!DeletedStudents::<Test>::contains_key(second));
+		assert!(DeletedStudents::<Test>::contains_key(first));
+		assert_eq!(PurgeAgenda::<Test>::get(6).into_inner(), vec![first]);
+		assert_eq!(NextPurgeBlock::<Test>::get(), 6);
+
+		assert_eq!(Template::on_idle(6, Weight::zero()), Weight::zero());
+		Template::on_idle(6, Weight::MAX);
+		assert!(!DeletedStudents::<Test>::contains_key(first));
+	});
+}
+
+#[test]
+fn owners_are_capped_and_paginated() {
+	new_test_ext().execute_with(|| {
+		let mut data = ada();
+		for name in [&b"Ada"[..], b"Grace", b"Mary"] {
+			data.given_name = name.to_vec();
+			assert_ok!(Template::create_student(
+				RuntimeOrigin::signed(1),
+				UNIVERSITY,
+				data.clone()
+			));
+		}
+		data.given_name = b"Emmy".to_vec();
+		assert_noop!(
+			Template::create_student(RuntimeOrigin::signed(1), UNIVERSITY, data),
+			Error::<Test>::MaxStudentsReached
+		);
+		assert_eq!(crate::OwnedStudentCount::<Test>::get(1), 3);
+
+		let first = Pallet::<Test>::students_of(1, None, 2);
+		assert_eq!(first.len(), 2);
+		let rest = Pallet::<Test>::students_of(1, first.last().copied(), 2);
+		assert_eq!(rest.len(), 1);
+		let mut all = [first, rest].concat();
+		all.sort();
+		assert_eq!(all, vec![0, 1, 2]);
+
+		// Removing a student frees room and cleans up the indexes
+		assert_ok!(Template::delete_any_student(RuntimeOrigin::root(), 1));
+		assert_eq!(crate::OwnedStudentCount::<Test>::get(1), 2);
+		assert_eq!(crate::StudentOwner::<Test>::get(1), None);
+		assert!(!Pallet::<Test>::owns(&1, 1));
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn institutions_scope_enrolment_and_admin_actions() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(Template::cause_error(RuntimeOrigin::signed(1)), Error::<Test>::NoneValue);
+		// Only the registrar registers and accredits institutions
+		assert_noop!(
+			Template::register_institution(RuntimeOrigin::signed(3), b"Other".to_vec(), vec![3]),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Template::register_institution(
+			RuntimeOrigin::root(),
+			b"Other".to_vec(),
+			vec![3]
+		));
+		System::assert_last_event(Event::InstitutionRegistered { institution_id: 1 }.into());
+
+		assert_noop!(
+			Template::create_student(RuntimeOrigin::signed(3), UNIVERSITY, ada()),
+			Error::<Test>::NotInstitutionAdmin
+		);
+		assert_noop!(
+			Template::create_student(RuntimeOrigin::signed(3), 1, ada()),
+			Error::<Test>::NotAccredited
+		);
+		assert_noop!(
+			Template::set_accreditation(RuntimeOrigin::signed(3), 1, Accreditation::Accredited),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Template::set_accreditation(
+			RuntimeOrigin::root(),
+			1,
+			Accreditation::Accredited
+		));
+		assert_ok!(Template::create_student(RuntimeOrigin::signed(3), 1, ada()));
+		assert_eq!(StudentInstitution::<Test>::get(0), Some(1));
+
+		// Admins only act on the students of their own institution
+		assert_noop!(
+			Template::delete_any_student(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::NotInstitutionAdmin
+		);
+		// Only the registrar changes who administers an institution
+		assert_noop!(
+			Template::set_institution_admins(RuntimeOrigin::signed(3), 1, vec![3, 4]),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Template::set_institution_admins(RuntimeOrigin::root(), 1, vec![3, 4]));
+		assert_ok!(Template::delete_any_student(RuntimeOrigin::signed(4), 0));
+		assert_ok!(Template::restore_student(RuntimeOrigin::signed(4), 0));
+
+		// Suspended institutions cannot graduate their students
+		assert_ok!(Template::set_accreditation(RuntimeOrigin::root(), 1, Accreditation::Suspended));
+		assert_noop!(
+			Template::graduate_student(RuntimeOrigin::signed(3), 0, None),
+			Error::<Test>::NotAccredited
+		);
+	});
+}
+
+#[test]
+fn removed_admins_lose_control_of_their_students() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::set_institution_admins(RuntimeOrigin::root(), UNIVERSITY, vec![2]));
+
+		assert_noop!(
+			Template::update_student(RuntimeOrigin::signed(1), id, ada()),
+			Error::<Test>::NotInstitutionAdmin
+		);
+		assert_noop!(
+			Template::graduate_student(RuntimeOrigin::signed(1), id, None),
+			Error::<Test>::NotInstitutionAdmin
+		);
+		assert_noop!(
+			Template::suspend_student(RuntimeOrigin::signed(1), id),
+			Error::<Test>::NotInstitutionAdmin
+		);
+		assert_noop!(
+			Template::delete_student(RuntimeOrigin::signed(1), id),
+			Error::<Test>::NotInstitutionAdmin
+		);
+
+		// Back in office, the admin controls the student again
+		assert_ok!(Template::set_institution_admins(RuntimeOrigin::root(), UNIVERSITY, vec![1]));
+		assert_ok!(Template::delete_student(RuntimeOrigin::signed(1), id));
+	});
+}
+
+#[test]
+fn graduates_go_to_their_institution_destination() {
+	new_test_ext().execute_with(|| {
+		let destination = GraduationDestination { para_id: 3000, institution: 5 };
+		assert_noop!(
+			Template::set_graduation_destination(
+				RuntimeOrigin::signed(3),
+				UNIVERSITY,
+				Some(destination.clone())
+			),
+			Error::<Test>::NotInstitutionAdmin
+		);
+		assert_ok!(Template::set_graduation_destination(
+			RuntimeOrigin::signed(2),
+			UNIVERSITY,
+			Some(destination)
+		));
+
+		let id = create(1);
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+		assert_eq!(StudentInstitution::<Test>::get(id), None);
+
+		let (dest, message) = &sent_xcm()[0];
+		assert_eq!(*dest, Location::new(1, [Parachain(3000)]));
+		let Some(Transact { call, .. }) = message.0.iter().rfind(|i| matches!(i, Transact { .. }))
+		else {
+			panic!("no transact")
+		};
+		let RuntimeCall::Template(crate::Call::receive_student { institution, .. }) =
+			RuntimeCall::decode(&mut &call.clone().into_encoded()[..]).unwrap()
+		else {
+			panic!("not a receive_student call")
+		};
+		assert_eq!(institution, 5);
+
+		// The receiving chain only accepts institutions it hosts
+		announce(id);
+		assert_noop!(
+			Template::receive_student(
+				xcm(sibling_account(1000, 7)),
+				student(),
+				id,
+				None,
+				Default::default(),
+				5
+			),
+			Error::<Test>::InstitutionNotFound
+		);
+		announce(id);
+		assert_ok!(Template::receive_student(
+			xcm(sibling_account(1000, 7)),
+			student(),
+			id,
+			None,
+			Default::default(),
+			UNIVERSITY
+		));
+		assert_eq!(StudentInstitution::<Test>::get(id + 1), Some(UNIVERSITY));
+	});
+}
+
+#[test]
+fn received_graduates_can_be_hired_promoted_and_let_go() {
+	new_test_ext().execute_with(|| {
+		let hire = |who: u64, id: u32| {
+			Template::hire_student(
+				RuntimeOrigin::signed(who),
+				id,
+				UNIVERSITY,
+				b"Engineer".to_vec(),
+				b"R&D".to_vec(),
+				ContractType::Permanent,
+			)
+		};
+
+		// Local students cannot be hired
+		let mut grace = ada();
+		grace.given_name = b"Grace".to_vec();
+		assert_ok!(Template::create_student(RuntimeOrigin::signed(1), UNIVERSITY, grace));
+		let local = 0;
+		assert_noop!(hire(1, local), Error::<Test>::NotReceivedStudent);
+
+		announce(3);
+		assert_ok!(Template::receive_student(
+			xcm(sibling_account(1000, 7)),
+			student(),
+			3,
+			None,
+			Default::default(),
+			UNIVERSITY
+		));
+		let id = local + 1;
+		assert_noop!(hire(3, id), Error::<Test>::NotInstitutionAdmin);
+		assert_ok!(hire(1, id));
+		assert_eq!(Students::<Test>::get(id).unwrap().status, StudentStatus::Employed);
+		assert_eq!(Pallet::<Test>::employment(id).unwrap().start, 1);
+		assert_noop!(
+			Template::set_employed(RuntimeOrigin::signed(1_000_007), id, false),
+			Error::<Test>::HasEmployment
+		);
+
+		System::set_block_number(4);
+		assert_noop!(
+			Template::promote_employee(
+				RuntimeOrigin::signed(2),
+				id,
+				b"Lead engineer".to_vec(),
+				b"Research and development".to_vec(),
+				ContractType::Permanent,
+			),
+			Error::<Test>::PositionTooLong
+		);
+		assert_ok!(Template::promote_employee(
+			RuntimeOrigin::signed(2),
+			id,
+			b"Lead engineer".to_vec(),
+			b"R&D".to_vec(),
+			ContractType::Permanent,
+		));
+		let employment = Pallet::<Test>::employment(id).unwrap();
+		assert_eq!(employment.position.title.into_inner(), b"Lead engineer".to_vec());
+		assert_eq!(employment.promoted_at, Some(4));
+		assert_eq!(Pallet::<Test>::student_timeline(id).last().unwrap().change, Change::Promoted);
+
+		assert_ok!(Template::terminate_employment(RuntimeOrigin::signed(1), id));
+		System::assert_last_event(
+			Event::EmploymentTerminated { student_id: id, employer: UNIVERSITY }.into(),
+		);
+		assert_eq!(Students::<Test>::get(id).unwrap().status, StudentStatus::Graduated);
+		assert_eq!(Pallet::<Test>::employment(id), None);
+		assert_noop!(
+			Template::terminate_employment(RuntimeOrigin::signed(1), id),
+			Error::<Test>::NotEmployed
+		);
+	});
+}
+
+#[test]
+fn job_offers_are_sent_to_students_of_sibling_chains() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Template::send_job_offer(
+				RuntimeOrigin::signed(3),
+				UNIVERSITY,
+				1000,
+				7,
+				b"Engineer".to_vec(),
+				b"R&D".to_vec(),
+				ContractType::Internship,
+			),
+			Error::<Test>::NotInstitutionAdmin
+		);
+		assert_ok!(Template::send_job_offer(
+			RuntimeOrigin::signed(1),
+			UNIVERSITY,
+			1000,
+			7,
+			b"Engineer".to_vec(),
+			b"R&D".to_vec(),
+			ContractType::Internship,
+		));
+
+		let (dest, message) = &sent_xcm()[0];
+		assert_eq!(*dest, Location::new(1, [Parachain(1000)]));
+		let Some(Transact { call, .. }) = message.0.last() else { panic!("no transact") };
+		let RuntimeCall::Template(crate::Call::receive_job_offer {
+			student_id,
+			employer,
+			position,
+		}) = RuntimeCall::decode(&mut &call.clone().into_encoded()[..]).unwrap()
+		else {
+			panic!("not a receive_job_offer call")
+		};
+		assert_eq!((student_id, employer), (7, UNIVERSITY));
+		assert_eq!(position.contract, ContractType::Internship);
+	});
+}
+
+#[test]
+fn accepting_a_job_offer_graduates_to_the_employer() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::set_student_account(RuntimeOrigin::signed(1), id, Some(5)));
+		let position = crate::Position::<Test> {
+			title: b"Engineer".to_vec().try_into().unwrap(),
+			department: b"R&D".to_vec().try_into().unwrap(),
+			contract: ContractType::Permanent,
+		};
+		let offer = |para: u32, employer: u32| {
+			Template::receive_job_offer(
+				xcm(Location::new(1, [Parachain(para)])),
+				id,
+				employer,
+				position.clone(),
+			)
+		};
+
+		assert_noop!(
+			Template::receive_job_offer(RuntimeOrigin::signed(1), id, 0, position.clone()),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Template::receive_job_offer(xcm(sibling_account(3000, 7)), id, 0, position.clone()),
+			Error::<Test>::NotFromSiblingChain
+		);
+		assert_ok!(offer(3000, 0));
+		assert_ok!(offer(3000, 0));
+		assert_ok!(offer(3001, 4));
+		assert_noop!(offer(3001, 5), Error::<Test>::TooManyJobOffersFromPara);
+		assert_noop!(offer(3002, 0), Error::<Test>::TooManyJobOffers);
+		assert_eq!(Pallet::<Test>::job_offers(id).len(), 2);
+
+		// Only the student decides
+		assert_noop!(
+			Template::decline_job_offer(RuntimeOrigin::signed(1), id, 3001, 4),
+			Error::<Test>::NotStudentAccount
+		);
+		assert_ok!(Template::decline_job_offer(RuntimeOrigin::signed(5), id, 3001, 4));
+		assert_noop!(
+			Template::accept_job_offer(RuntimeOrigin::signed(5), id, 3001, 4, None),
+			Error::<Test>::NoJobOffer
+		);
+
+		assert_ok!(Template::accept_job_offer(RuntimeOrigin::signed(5), id, 3000, 0, None));
+		assert_eq!(Alumni::<Test>::get(id).unwrap().destination_para, 3000);
+		assert_eq!(Alumni::<Test>::get(id).unwrap().graduated_by, 1);
+		assert!(Pallet::<Test>::job_offers(id).is_empty());
+
+		let (dest, message) = &sent_xcm()[0];
+		assert_eq!(*dest, Location::new(1, [Parachain(3000)]));
+		let Some(Transact { call, .. }) = message.0.iter().rfind(|i| matches!(i, Transact { .. }))
+		else {
+			panic!("no transact")
+		};
+		let RuntimeCall::Template(crate::Call::receive_student { institution, .. }) =
+			RuntimeCall::decode(&mut &call.clone().into_encoded()[..]).unwrap()
+		else {
+			panic!("not a receive_student call")
+		};
+		assert_eq!(institution, 0);
+	});
+}
+
+#[test]
+fn verification_requests_are_answered_by_the_source_chain() {
+	new_test_ext().execute_with(|| {
+		announce(3);
+		assert_ok!(Template::receive_student(
+			xcm(sibling_account(1000, 7)),
+			student(),
+			3,
+			None,
+			Default::default(),
+			UNIVERSITY
+		));
+		assert_noop!(
+			Template::request_verification(RuntimeOrigin::signed(3), 0),
+			Error::<Test>::NotStudentOwner
+		);
+		assert_ok!(Template::request_verification(RuntimeOrigin::signed(1_000_007), 0));
+		System::assert_last_event(
+			Event::VerificationRequested { student_id: 0, query_id: 0 }.into(),
+		);
+		assert_eq!(queries(), vec![(Location::new(1, [Parachain(1000)]), 21)]);
+
+		// The source chain checks the record as it was sent, and reports back to this chain
+		let (dest, message) = &sent_xcm()[0];
+		assert_eq!(*dest, Location::new(1, [Parachain(1000)]));
+		let [.., Transact { call, .. }, ReportTransactStatus(info)] = &message.0[..] else {
+			panic!("no reported transact")
+		};
+		assert_eq!(info.destination, Location::new(1, [Parachain(2000)]));
+		assert_eq!(info.query_id, 0);
+		assert_eq!(
+			RuntimeCall::decode(&mut &call.clone().into_encoded()[..]).unwrap(),
+			RuntimeCall::Template(crate::Call::verify_diploma {
+				student_id: 3,
+				diploma_hash: BlakeTwo256::hash_of(&student()),
+			})
+		);
+
+		let source = Location::new(1, [Parachain(1000)]);
+		let success = Response::DispatchResult(MaybeErrorCode::Success);
+		assert_noop!(
+			Template::verification_response(xcm(source.clone()), 0, success.clone()),
+			DispatchError::BadOrigin
+		);
+		System::set_block_number(3);
+		assert_ok!(Template::verification_response(response(source.clone()), 0, success.clone()));
+		assert_eq!(Pallet::<Test>::verification(0), Some(Verification { valid: true, at: 3 }));
+		assert_noop!(
+			Template::verification_response(response(source), 0, success),
+			Error::<Test>::UnknownQuery
+		);
+	});
+}
+
+#[test]
+fn unanswered_verification_requests_expire() {
+	new_test_ext().execute_with(|| {
+		announce(3);
+		assert_ok!(Template::receive_student(
+			xcm(sibling_account(1000, 7)),
+			student(),
+			3,
+			None,
+			Default::default(),
+			UNIVERSITY
+		));
+		let owner = RuntimeOrigin::signed(1_000_007);
+		assert_ok!(Template::request_verification(owner.clone(), 0));
+		assert_ok!(Template::request_verification(owner.clone(), 0));
+		assert_noop!(
+			Template::request_verification(owner.clone(), 0),
+			Error::<Test>::TooManyPendingVerifications
+		);
+
+		// Answered requests no longer count
+		let source = Location::new(1, [Parachain(1000)]);
+		let success = Response::DispatchResult(MaybeErrorCode::Success);
+		assert_ok!(Template::verification_response(response(source.clone()), 0, success.clone()));
+		Template::on_idle(1, Weight::MAX);
+		System::set_block_number(2);
+		assert_ok!(Template::request_verification(owner.clone(), 0));
+
+		// The others are dropped once their answer is late
+		System::set_block_number(21);
+		Template::on_idle(21, Weight::MAX);
+		assert_eq!(PendingVerifications::<Test>::iter().count(), 2);
+		System::set_block_number(22);
+		Template::on_idle(22, Weight::MAX);
+		System::assert_last_event(Event::VerificationExpired { student_id: 0, query_id: 1 }.into());
+		assert_eq!(PendingVerificationCount::<Test>::get(0), 1);
+		System::set_block_number(23);
+		Template::on_idle(23, Weight::MAX);
+		System::assert_last_event(Event::VerificationExpired { student_id: 0, query_id: 2 }.into());
+		assert_eq!(PendingVerifications::<Test>::iter().count(), 0);
+		assert!(!PendingVerificationCount::<Test>::contains_key(0));
+		assert_noop!(
+			Template::verification_response(response(source), 1, success),
+			Error::<Test>::UnknownQuery
+		);
+		assert_ok!(Template::request_verification(owner, 0));
+	});
+}
+
+#[test]
+fn verify_diploma_checks_the_alumni_record() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+		let diploma_hash = Alumni::<Test>::get(id).unwrap().diploma_hash;
+		let destination = xcm(Location::new(1, [Parachain(2000)]));
+
+		assert_ok!(Template::verify_diploma(destination.clone(), id, diploma_hash));
+		assert_noop!(
+			Template::verify_diploma(destination, id, H256::zero()),
+			Error::<Test>::DiplomaMismatch
+		);
+		assert_noop!(
+			Template::verify_diploma(xcm(Location::new(1, [Parachain(3000)])), id, diploma_hash),
+			Error::<Test>::NotAlumnus
+		);
+		assert_noop!(
+			Template::verify_diploma(RuntimeOrigin::signed(1), id, diploma_hash),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Template::verify_diploma(xcm(sibling_account(2000, 7)), id, diploma_hash),
+			Error::<Test>::NotFromSiblingChain
+		);
+	});
+}
+
+#[test]
+fn revoked_diplomas_are_marked_on_the_destination() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_noop!(
+			Template::revoke_diploma(RuntimeOrigin::signed(1), id),
+			Error::<Test>::NotAlumnus
+		);
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+		let diploma_hash = Alumni::<Test>::get(id).unwrap().diploma_hash;
+
+		assert_noop!(
+			Template::revoke_diploma(RuntimeOrigin::signed(2), id),
+			Error::<Test>::NotStudentOwner
+		);
+		System::set_block_number(2);
+		assert_ok!(Template::revoke_diploma(RuntimeOrigin::signed(1), id));
+		assert_eq!(Pallet::<Test>::diploma_revoked_at(id), Some(2));
+		assert_noop!(
+			Template::revoke_diploma(RuntimeOrigin::root(), id),
+			Error::<Test>::DiplomaRevoked
+		);
+		assert_noop!(
+			Template::verify_diploma(xcm(Location::new(1, [Parachain(2000)])), id, diploma_hash),
+			Error::<Test>::DiplomaRevoked
+		);
+
+		let (dest, message) = sent_xcm().pop().unwrap();
+		assert_eq!(dest, Location::new(1, [Parachain(2000)]));
+		let Some(Transact { call, .. }) = message.0.last() else { panic!("no transact") };
+		assert_eq!(
+			RuntimeCall::decode(&mut &call.clone().into_encoded()[..]).unwrap(),
+			RuntimeCall::Template(crate::Call::receive_revocation { source_id: id })
+		);
+
+		// On the destination, the received copy is marked revoked and can no longer be hired
+		let sender = xcm(Location::new(1, [Parachain(1000)]));
+		assert_noop!(
+			Template::receive_revocation(sender.clone(), 3),
+			Error::<Test>::StudentNotFound
+		);
+		announce(3);
+		assert_ok!(Template::receive_student(
+			xcm(sibling_account(1000, 7)),
+			student(),
+			3,
+			None,
+			Default::default(),
+			UNIVERSITY
+		));
+		let received = id + 1;
+		assert_noop!(
+			Template::receive_revocation(xcm(sibling_account(1000, 7)), 3),
+			Error::<Test>::NotFromSiblingChain
+		);
+		assert_ok!(Template::receive_revocation(sender, 3));
+		System::assert_last_event(
+			Event::ReceivedDiplomaRevoked { student_id: received, source_para: 1000 }.into(),
+		);
+		assert_eq!(Pallet::<Test>::diploma_revoked_at(received), Some(2));
+		assert_eq!(
+			Pallet::<Test>::student_timeline(received).last().unwrap().change,
+			Change::Revoked
+		);
+		assert_noop!(
+			Template::hire_student(
+				RuntimeOrigin::signed(1),
+				received,
+				UNIVERSITY,
+				b"Engineer".to_vec(),
+				b"R&D".to_vec(),
+				ContractType::Permanent,
+			),
+			Error::<Test>::DiplomaRevoked
+		);
+	});
+}
+
+#[test]
+fn subscribed_destinations_receive_amendments() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+		let destination = xcm(Location::new(1, [Parachain(2000)]));
+		let delta = crate::PersonalDataDelta::<Test> {
+			family_name: Some(b"King".to_vec().try_into().unwrap()),
+			email_hash: Some(Some(H256::repeat_byte(1))),
+			..Default::default()
+		};
+
+		// Only the destination of the alumnus can subscribe
+		assert_noop!(
+			Template::amend_alumnus(RuntimeOrigin::signed(1), id, delta.clone()),
+			Error::<Test>::NotSubscribed
+		);
+		assert_noop!(
+			Template::receive_subscription(xcm(Location::new(1, [Parachain(3000)])), id, true),
+			Error::<Test>::NotAlumnus
+		);
+		assert_noop!(
+			Template::receive_subscription(xcm(sibling_account(2000, 7)), id, true),
+			Error::<Test>::NotFromSiblingChain
+		);
+		assert_ok!(Template::receive_subscription(destination, id, true));
+		assert_noop!(
+			Template::amend_alumnus(RuntimeOrigin::signed(2), id, delta.clone()),
+			Error::<Test>::NotStudentOwner
+		);
+
+		// Amendments are checked before being sent
+		let invalid = crate::PersonalDataDelta::<Test> {
+			family_name: Some(Default::default()),
+			..Default::default()
+		};
+		assert_noop!(
+			Template::amend_alumnus(RuntimeOrigin::signed(1), id, invalid),
+			Error::<Test>::EmptySurname
+		);
+		let unborn = crate::PersonalDataDelta::<Test> {
+			date_of_birth: Some(Date { year: 2100, month: 1, day: 1 }),
+			..Default::default()
+		};
+		assert_noop!(
+			Template::amend_alumnus(RuntimeOrigin::signed(1), id, unborn),
+			Error::<Test>::DateOfBirthInFuture
+		);
+		assert_ok!(Template::amend_alumnus(RuntimeOrigin::signed(1), id, delta.clone()));
+
+		let (dest, message) = sent_xcm().pop().unwrap();
+		assert_eq!(dest, Location::new(1, [Parachain(2000)]));
+		let Some(Transact { call, .. }) = message.0.last() else { panic!("no transact") };
+		assert_eq!(
+			RuntimeCall::decode(&mut &call.clone().into_encoded()[..]).unwrap(),
+			RuntimeCall::Template(crate::Call::receive_update { source_id: id, delta })
+		);
+	});
+}
+
+#[test]
+fn updates_from_the_source_keep_fields_edited_locally() {
+	new_test_ext().execute_with(|| {
+		announce(3);
+		assert_ok!(Template::receive_student(
+			xcm(sibling_account(1000, 7)),
+			student(),
+			3,
+			None,
+			Default::default(),
+			UNIVERSITY
+		));
+		assert_ok!(Template::set_update_subscription(RuntimeOrigin::signed(1_000_007), 0, true));
+		let (_, message) = sent_xcm().pop().unwrap();
+		let Some(Transact { call, .. }) = message.0.last() else { panic!("no transact") };
+		assert_eq!(
+			RuntimeCall::decode(&mut &call.clone().into_encoded()[..]).unwrap(),
+			RuntimeCall::Template(crate::Call::receive_subscription {
+				source_id: 3,
+				subscribed: true,
+			})
+		);
+
+		// The e-mail is changed here, so it belongs to this chain from now on
+		let mut data = ada();
+		data.email_hash = Some(H256::repeat_byte(2));
+		assert_ok!(Template::update_student(RuntimeOrigin::signed(1_000_007), 0, data));
+
+		let delta = crate::PersonalDataDelta::<Test> {
+			family_name: Some(b"King".to_vec().try_into().unwrap()),
+			email_hash: Some(Some(H256::repeat_byte(1))),
+			..Default::default()
+		};
+		let source = xcm(Location::new(1, [Parachain(1000)]));
+		assert_noop!(
+			Template::receive_update(xcm(sibling_account(1000, 7)), 3, delta.clone()),
+			Error::<Test>::NotFromSiblingChain
+		);
+		assert_ok!(Template::receive_update(source.clone(), 3, delta));
+		System::assert_last_event(
+			Event::StudentUpdatedFromSource {
+				student_id: 0,
+				applied: PersonalDataFields::FAMILY_NAME,
+				kept: PersonalDataFields::EMAIL_HASH,
+			}
+			.into(),
+		);
+		let personal = Students::<Test>::get(0).unwrap().personal.unwrap();
+		assert_eq!(personal.family_name.into_inner(), b"King".to_vec());
+		assert_eq!(personal.email_hash, Some(H256::repeat_byte(2)));
+
+		// Updates are validated like local ones
+		let invalid = crate::PersonalDataDelta::<Test> {
+			given_name: Some(Default::default()),
+			..Default::default()
+		};
+		assert_noop!(Template::receive_update(source, 3, invalid), Error::<Test>::EmptyName);
+	});
+}
+
+#[test]
+fn students_are_items_of_their_institution() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(collections(), vec![UNIVERSITY]);
+		assert_ok!(Template::register_institution(RuntimeOrigin::root(), b"ETH".to_vec(), vec![3]));
+		assert_eq!(collections(), vec![UNIVERSITY, 1]);
+
+		// The item holds the record, and follows its changes
+		let id = create(1);
+		let metadata = || Students::<Test>::get(id).unwrap().encode();
+		assert_eq!(item(UNIVERSITY, id), Some((1, metadata())));
+
+		let mut data = ada();
+		data.middle_name = b"King".to_vec();
+		assert_ok!(Template::update_student(RuntimeOrigin::signed(1), id, data));
+		assert_eq!(item(UNIVERSITY, id), Some((1, metadata())));
+
+		assert_ok!(Template::erase_student_data(RuntimeOrigin::signed(1), id));
+		assert_eq!(Students::<Test>::get(id).unwrap().personal, None);
+		assert_eq!(item(UNIVERSITY, id), Some((1, metadata())));
+
+		// Purging the student burns it
+		assert_ok!(Template::delete_student(RuntimeOrigin::signed(1), id));
+		assert!(item(UNIVERSITY, id).is_some());
+		System::set_block_number(6);
+		Template::on_idle(6, Weight::MAX);
+		assert_eq!(item(UNIVERSITY, id), None);
+	});
+}
+
+#[test]
+fn graduation_reserves_the_item_for_the_destination() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Template::set_graduation_destination(
+			RuntimeOrigin::signed(2),
+			UNIVERSITY,
+			Some(GraduationDestination { para_id: 3000, institution: 5 }),
+		));
+		let id = create(1);
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+
+		// Kept by the sovereign account of the destination, naming the record only by its hash
+		let (owner, metadata) = item(UNIVERSITY, id).unwrap();
+		assert_eq!(owner, 3_000_000);
+		let alumnus = Pallet::<Test>::alumnus(id).unwrap();
+		assert_eq!(metadata, alumnus.diploma_hash.encode());
+		assert!(!metadata.windows(b"Lovelace".len()).any(|w| w == b"Lovelace"));
+
+		// The copy is deposited into the owner once the student is received
+		let (_, message) = &sent_xcm()[0];
+		let asset: Asset = (
+			Location::new(1, [Parachain(2000), PalletInstance(9), GeneralIndex(0)]),
+			Index(id.into()),
+		)
+			.into();
+		assert!(matches!(
+			message.0.as_slice(),
+			[
+				UnpaidExecution { .. },
+				ReserveAssetDeposited(assets),
+				Transact { .. },
+				DescendOrigin(_),
+				Transact { .. },
+				DepositAsset { beneficiary, .. },
+			] if *assets == asset.into() && *beneficiary == sibling_account(2000, 1)
+		));
+
+		// Erasing the alumnus wipes the reserved item too
+		assert_ok!(Template::erase_student_data(RuntimeOrigin::signed(1), id));
+		assert_eq!(item(UNIVERSITY, id), Some((3_000_000, vec![])));
+	});
+}
+
+#[test]
+fn items_go_to_the_default_destination_institution() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+
+		assert_eq!(item(UNIVERSITY, id).map(|(owner, _)| owner), Some(2_000_000));
+		let (_, message) = &sent_xcm()[0];
+		assert!(message.0.iter().any(|i| matches!(i, ReserveAssetDeposited(_))));
+		let Some(Transact { call, .. }) = message.0.iter().rfind(|i| matches!(i, Transact { .. }))
+		else {
+			panic!("no transact")
+		};
+		let RuntimeCall::Template(crate::Call::receive_student { institution, .. }) =
+			RuntimeCall::decode(&mut &call.clone().into_encoded()[..]).unwrap()
+		else {
+			panic!("not a receive_student call")
+		};
+		assert_eq!(institution, UNIVERSITY);
+	});
+}
+
+#[test]
+fn admins_top_up_the_scholarship_fund() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Template::fund_scholarships(RuntimeOrigin::signed(3), UNIVERSITY, 100),
+			Error::<Test>::NotInstitutionAdmin
+		);
+
+		assert_ok!(Template::fund_scholarships(RuntimeOrigin::signed(1), UNIVERSITY, 100));
+		assert_eq!(Balances::free_balance(Template::institution_account(UNIVERSITY)), 100);
+		assert_eq!(Balances::free_balance(1), ADMIN_BALANCE - 100);
+		System::assert_last_event(
+			Event::ScholarshipFundToppedUp { institution_id: UNIVERSITY, who: 1, amount: 100 }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn stipends_are_paid_every_period() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::fund_scholarships(RuntimeOrigin::signed(1), UNIVERSITY, 500));
+
+		// Paid to the student's own account
+		assert_noop!(
+			Template::award_stipend(RuntimeOrigin::signed(1), id, 100, false),
+			Error::<Test>::NoStudentAccount
+		);
+		assert_ok!(Template::set_student_account(RuntimeOrigin::signed(1), id, Some(5)));
+		assert_noop!(
+			Template::award_stipend(RuntimeOrigin::signed(3), id, 100, false),
+			Error::<Test>::NotInstitutionAdmin
+		);
+		assert_ok!(Template::award_stipend(RuntimeOrigin::signed(2), id, 100, false));
+		System::assert_last_event(
+			Event::StipendAwarded { student_id: id, amount: 100, first_payment: 11 }.into(),
+		);
+		assert_noop!(
+			Template::award_stipend(RuntimeOrigin::signed(1), id, 200, false),
+			Error::<Test>::StipendAlreadyAwarded
+		);
+
+		Template::on_initialize(10);
+		assert_eq!(Balances::free_balance(5), 0);
+		Template::on_initialize(11);
+		assert_eq!(Balances::free_balance(5), 100);
+		System::assert_last_event(
+			Event::StipendPaid { student_id: id, amount: 100, destination: None }.into(),
+		);
+
+		// Always to the account linked at the time of the payment
+		assert_ok!(Template::set_student_account(RuntimeOrigin::signed(5), id, Some(7)));
+		Template::on_initialize(21);
+		assert_eq!(Balances::free_balance(5), 100);
+		assert_eq!(Balances::free_balance(7), 100);
+		assert_eq!(Balances::free_balance(Template::institution_account(UNIVERSITY)), 300);
+	});
+}
+
+#[test]
+fn unpaid_stipends_are_retried_in_the_next_period() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::set_student_account(RuntimeOrigin::signed(1), id, Some(5)));
+		assert_ok!(Template::award_stipend(RuntimeOrigin::signed(1), id, 100, false));
+
+		Template::on_initialize(11);
+		assert_eq!(Balances::free_balance(5), 0);
+		assert!(matches!(
+			System::events().last().unwrap().event,
+			RuntimeEvent::Template(Event::StipendPaymentFailed { student_id, .. }) if student_id == id
+		));
+
+		assert_ok!(Template::fund_scholarships(RuntimeOrigin::signed(1), UNIVERSITY, 500));
+		Template::on_initialize(21);
+		assert_eq!(Balances::free_balance(5), 100);
+	});
+}
+
+#[test]
+fn revoked_stipends_end_and_deleted_ones_pause() {
+	new_test_ext().execute_with(|| {
+		let revoked = create(1);
+		let deleted = create_another(1);
+		assert_ok!(Template::fund_scholarships(RuntimeOrigin::signed(1), UNIVERSITY, 500));
+		for (id, account) in [(revoked, 5), (deleted, 6)] {
+			assert_ok!(Template::set_student_account(RuntimeOrigin::signed(1), id, Some(account)));
+			assert_ok!(Template::award_stipend(RuntimeOrigin::signed(1), id, 100, false));
+		}
+
+		assert_noop!(
+			Template::revoke_stipend(RuntimeOrigin::signed(3), revoked),
+			Error::<Test>::NotInstitutionAdmin
+		);
+		assert_ok!(Template::revoke_stipend(RuntimeOrigin::signed(2), revoked));
+		assert!(!Stipends::<Test>::contains_key(revoked));
+		assert_eq!(StipendAgenda::<Test>::get(11).into_inner(), vec![deleted]);
+
+		// Not paid while deleted, but kept for a restore
+		assert_ok!(Template::delete_student(RuntimeOrigin::signed(1), deleted));
+		Template::on_initialize(11);
+		assert_eq!(Balances::free_balance(5), 0);
+		assert_eq!(Balances::free_balance(6), 0);
+		assert_ok!(Template::restore_student(RuntimeOrigin::signed(1), deleted));
+		Template::on_initialize(21);
+		assert_eq!(Balances::free_balance(6), 100);
+
+		// Purged students are no longer paid
+		assert_ok!(Template::delete_student(RuntimeOrigin::signed(1), deleted));
+		System::set_block_number(6);
+		Template::on_idle(6, Weight::MAX);
+		Template::on_initialize(31);
+		System::assert_last_event(Event::StipendEnded { student_id: deleted }.into());
+		assert_eq!(Balances::free_balance(6), 100);
+		assert!(StipendAgenda::<Test>::get(41).is_empty());
+	});
+}
+
+#[test]
+fn stipends_continue_on_the_destination_after_graduation() {
+	new_test_ext().execute_with(|| {
+		let continued = create(1);
+		let ended = create_another(1);
+		assert_ok!(Template::set_graduation_destination(
+			RuntimeOrigin::signed(1),
+			UNIVERSITY,
+			Some(GraduationDestination { para_id: 3000, institution: 5 }),
+		));
+		assert_ok!(Template::fund_scholarships(RuntimeOrigin::signed(1), UNIVERSITY, 500));
+		for (id, account, after_graduation) in [(continued, 5, true), (ended, 6, false)] {
+			assert_ok!(Template::set_student_account(RuntimeOrigin::signed(1), id, Some(account)));
+			assert_ok!(Template::award_stipend(
+				RuntimeOrigin::signed(1),
+				id,
+				100,
+				after_graduation
+			));
+			assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+			assert_ok!(Template::approve_transfer(RuntimeOrigin::signed(account), id));
+		}
+		assert!(!Stipends::<Test>::contains_key(ended));
+		SENT_XCM.with(|q| q.borrow_mut().clear());
+
+		// Sending the reserve transfer is weighed on top of the payment
+		let db = <Test as frame_system::Config>::DbWeight::get();
+		assert_eq!(
+			Template::on_initialize(11),
+			db.reads_writes(8, 5).saturating_add(StipendSendWeight::get())
+		);
+		let destination = Location::new(1, [Parachain(3000)]);
+		System::assert_last_event(
+			Event::StipendPaid {
+				student_id: continued,
+				amount: 100,
+				destination: Some(destination.clone()),
+			}
+			.into(),
+		);
+
+		// Reserve transferred to the student's account on the destination, whose sovereign
+		// account keeps the tokens
+		assert_eq!(Balances::free_balance(3_000_000), 100);
+		let [(to, message)] = &sent_xcm()[..] else { panic!("one message is sent") };
+		assert_eq!(*to, destination);
+		let asset: Asset = (Location::new(1, [Parachain(2000)]), 100u128).into();
+		assert!(matches!(
+			message.0.as_slice(),
+			[
+				UnpaidExecution { .. },
+				ReserveAssetDeposited(assets),
+				ClearOrigin,
+				DepositAsset { beneficiary, .. },
+			] if *assets == asset.into() &&
+				*beneficiary == Location::new(0, [AccountIndex64 { network: None, index: 5 }])
+		));
+	});
+}
+
+#[test]
+fn graduation_waits_for_tuition_to_be_paid() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_noop!(
+			Template::issue_invoice(RuntimeOrigin::signed(3), id, 300, 20),
+			Error::<Test>::NotInstitutionAdmin
+		);
+		assert_ok!(Template::issue_invoice(RuntimeOrigin::signed(2), id, 300, 20));
+		System::assert_last_event(
+			Event::InvoiceIssued { student_id: id, invoice_id: 0, amount: 300, due: 20 }.into(),
+		);
+		assert_ok!(Template::issue_invoice(RuntimeOrigin::signed(2), id, 200, 30));
+		assert_noop!(
+			Template::issue_invoice(RuntimeOrigin::signed(2), id, 100, 40),
+			Error::<Test>::TooManyInvoices
+		);
+
+		assert_noop!(
+			Template::graduate_student(RuntimeOrigin::signed(1), id, None),
+			Error::<Test>::TuitionUnpaid
+		);
+
+		// Anyone can pay, into the account of the institution
+		assert_ok!(Template::pay_invoice(RuntimeOrigin::signed(2), id, 0));
+		System::assert_last_event(
+			Event::InvoicePaid { student_id: id, invoice_id: 0, who: 2 }.into(),
+		);
+		assert_eq!(Balances::free_balance(Template::institution_account(UNIVERSITY)), 300);
+		assert_eq!(Balances::free_balance(2), ADMIN_BALANCE - 300);
+		assert_noop!(
+			Template::pay_invoice(RuntimeOrigin::signed(1), id, 0),
+			Error::<Test>::NoInvoice
+		);
+		assert_noop!(
+			Template::graduate_student(RuntimeOrigin::signed(1), id, None),
+			Error::<Test>::TuitionUnpaid
+		);
+
+		assert_ok!(Template::pay_invoice(RuntimeOrigin::signed(1), id, 1));
+		assert!(Template::unpaid_invoices(id).is_empty());
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+	});
+}
+
+#[test]
+fn admins_cancel_invoices() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::issue_invoice(RuntimeOrigin::signed(1), id, 300, 20));
+
+		// Payers need the funds, keeping their account alive
+		assert_noop!(
+			Template::pay_invoice(RuntimeOrigin::signed(3), id, 0),
+			TokenError::FundsUnavailable
+		);
+		assert_noop!(
+			Template::cancel_invoice(RuntimeOrigin::signed(3), id, 0),
+			Error::<Test>::NotInstitutionAdmin
+		);
+		assert_ok!(Template::cancel_invoice(RuntimeOrigin::signed(2), id, 0));
+		System::assert_last_event(Event::InvoiceCancelled { student_id: id, invoice_id: 0 }.into());
+		assert!(Template::unpaid_invoices(id).is_empty());
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+	});
+}
+
+#[test]
+fn committee_approves_graduations() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_noop!(
+			Template::set_graduation_committee(RuntimeOrigin::signed(1), UNIVERSITY, vec![5], 1),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Template::set_graduation_committee(RuntimeOrigin::root(), UNIVERSITY, vec![5], 2),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			Template::set_graduation_committee(RuntimeOrigin::root(), UNIVERSITY, vec![5, 5], 1),
+			Error::<Test>::DuplicateCommitteeMember
+		);
+		assert_noop!(
+			Template::set_graduation_committee(
+				RuntimeOrigin::root(),
+				UNIVERSITY,
+				vec![5, 6, 7, 8],
+				2
+			),
+			Error::<Test>::TooManyCommitteeMembers
+		);
+		assert_ok!(Template::set_graduation_committee(
+			RuntimeOrigin::root(),
+			UNIVERSITY,
+			vec![5, 6, 7],
+			2
+		));
+
+		// The owner alone only requests the graduation
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+		System::assert_last_event(
+			Event::GraduationApprovalRequested { student_id: id, expires_at: 11 }.into(),
+		);
+		assert!(sent_xcm().is_empty());
+		assert_noop!(
+			Template::graduate_student(RuntimeOrigin::signed(1), id, None),
+			Error::<Test>::GraduationAlreadyPending
+		);
+
+		assert_noop!(
+			Template::approve_graduation(RuntimeOrigin::signed(1), id),
+			Error::<Test>::NotCommitteeMember
+		);
+		assert_ok!(Template::approve_graduation(RuntimeOrigin::signed(5), id));
+		System::assert_last_event(
+			Event::GraduationApproved { student_id: id, who: 5, approvals: 1 }.into(),
+		);
+		assert_noop!(
+			Template::approve_graduation(RuntimeOrigin::signed(5), id),
+			Error::<Test>::AlreadyApproved
+		);
+		assert!(sent_xcm().is_empty());
+
+		// The threshold sends the student
+		assert_ok!(Template::approve_graduation(RuntimeOrigin::signed(7), id));
+		assert_eq!(sent_xcm().len(), 1);
+		assert!(Template::pending_graduation(id).is_none());
+		assert!(Students::<Test>::get(id).is_none());
+	});
+}
+
+#[test]
+fn pending_graduations_expire() {
+	new_test_ext().execute_with(|| {
+		let id = create(1);
+		assert_ok!(Template::set_graduation_committee(
+			RuntimeOrigin::root(),
+			UNIVERSITY,
+			vec![5, 6],
+			1
+		));
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+
+		System::set_block_number(12);
+		assert_noop!(
+			Template::approve_graduation(RuntimeOrigin::signed(5), id),
+			Error::<Test>::GraduationExpired
+		);
+
+		// The owner can request again
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+		System::assert_last_event(
+			Event::GraduationApprovalRequested { student_id: id, expires_at: 22 }.into(),
+		);
+
+		// Members that left no longer approve, and without a committee the owner graduates alone
+		assert_ok!(Template::set_graduation_committee(
+			RuntimeOrigin::root(),
+			UNIVERSITY,
+			vec![6],
+			1
+		));
+		assert_noop!(
+			Template::approve_graduation(RuntimeOrigin::signed(5), id),
+			Error::<Test>::NotCommitteeMember
+		);
+		assert_noop!(
+			Template::set_graduation_committee(RuntimeOrigin::signed(2), UNIVERSITY, vec![], 0),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Template::set_graduation_committee(
+			RuntimeOrigin::root(),
+			UNIVERSITY,
+			vec![],
+			0
+		));
+		System::set_block_number(23);
+		assert_ok!(Template::graduate_student(RuntimeOrigin::signed(1), id, None));
+		assert_eq!(sent_xcm().len(), 1);
 	});
 }
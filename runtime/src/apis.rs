@@ -26,8 +26,9 @@
 // External crates imports
 use alloc::vec::Vec;
 
-use polkadot_sdk::*;
+use polkadot_sdk::{staging_xcm as xcm, *};
 
+use cumulus_primitives_utility::ChargeWeightInFungibles;
 use frame_support::{
 	genesis_builder_helper::{build_state, get_preset},
 	weights::Weight,
@@ -42,11 +43,21 @@ use sp_runtime::{
 	ApplyExtrinsicResult,
 };
 use sp_version::RuntimeVersion;
+use xcm::{
+	latest::prelude::AssetId, Version as XcmVersion, VersionedAssetId, VersionedAssets,
+	VersionedLocation, VersionedXcm,
+};
+use xcm_runtime_apis::{
+	dry_run::{CallDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects},
+	fees::Error as XcmPaymentApiError,
+};
 
 // Local module imports
 use super::{
-	AccountId, Balance, Block, ConsensusHook, Executive, InherentDataExt, Nonce, ParachainSystem,
-	Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment,
+	configs::{xcm_config, ForeignAssetsInstance},
+	AccountId, Balance, Block, BlockNumber, ConsensusHook, Executive, Hash, InherentDataExt, Nonce,
+	OriginCaller, ParachainSystem, PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent,
+	RuntimeGenesisConfig, SessionKeys, System, TemplatePallet, TransactionPayment, WeightToFee,
 	SLOT_DURATION, VERSION,
 };
 
@@ -219,6 +230,77 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl xcm_runtime_apis::fees::XcmPaymentApi<Block> for Runtime {
+		fn query_acceptable_payment_assets(xcm_version: XcmVersion) -> Result<Vec<VersionedAssetId>, XcmPaymentApiError> {
+			// The native token and the registered tokens of siblings with a rate, as accepted by the
+			// `Trader` of `XcmConfig`, can pay for execution.
+			let foreign_assets = pallet_assets::Asset::<Runtime, ForeignAssetsInstance>::iter_keys()
+				.filter(|asset| pallet_asset_rate::ConversionRateToNative::<Runtime>::contains_key(asset))
+				.map(AssetId);
+			let acceptable_assets = core::iter::once(AssetId(xcm_config::HereLocation::get()))
+				.chain(foreign_assets)
+				.collect();
+			PolkadotXcm::query_acceptable_payment_assets(xcm_version, acceptable_assets)
+		}
+
+		fn query_weight_to_asset_fee(weight: Weight, asset: VersionedAssetId) -> Result<u128, XcmPaymentApiError> {
+			use frame_support::weights::WeightToFee as _;
+
+			let latest_asset_id: Result<AssetId, ()> = asset.clone().try_into();
+			match latest_asset_id {
+				Ok(asset_id) if asset_id.0 == xcm_config::HereLocation::get() => {
+					Ok(WeightToFee::weight_to_fee(&weight))
+				},
+				Ok(asset_id) if pallet_assets::Asset::<Runtime, ForeignAssetsInstance>::contains_key(&asset_id.0) => {
+					xcm_config::ForeignAssetsFeeCharger::charge_weight_in_fungibles(asset_id.0, weight)
+						.map_err(|_| XcmPaymentApiError::WeightNotComputable)
+				},
+				Ok(asset_id) => {
+					log::trace!(target: "xcm::xcm_runtime_apis", "query_weight_to_asset_fee - unhandled asset_id: {asset_id:?}!");
+					Err(XcmPaymentApiError::AssetNotFound)
+				},
+				Err(_) => {
+					log::trace!(target: "xcm::xcm_runtime_apis", "query_weight_to_asset_fee - failed to convert asset: {asset:?}!");
+					Err(XcmPaymentApiError::VersionedConversionFailed)
+				}
+			}
+		}
+
+		fn query_xcm_weight(message: VersionedXcm<()>) -> Result<Weight, XcmPaymentApiError> {
+			PolkadotXcm::query_xcm_weight(message)
+		}
+
+		fn query_delivery_fees(destination: VersionedLocation, message: VersionedXcm<()>) -> Result<VersionedAssets, XcmPaymentApiError> {
+			PolkadotXcm::query_delivery_fees(destination, message)
+		}
+	}
+
+	impl xcm_runtime_apis::dry_run::DryRunApi<Block, RuntimeCall, RuntimeEvent, OriginCaller> for Runtime {
+		fn dry_run_call(origin: OriginCaller, call: RuntimeCall, result_xcms_version: XcmVersion) -> Result<CallDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
+			PolkadotXcm::dry_run_call::<Runtime, xcm_config::XcmRouter, OriginCaller, RuntimeCall>(origin, call, result_xcms_version)
+		}
+
+		fn dry_run_xcm(origin_location: VersionedLocation, xcm: VersionedXcm<RuntimeCall>) -> Result<XcmDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
+			PolkadotXcm::dry_run_xcm::<Runtime, xcm_config::XcmRouter, RuntimeCall, xcm_config::XcmConfig>(origin_location, xcm)
+		}
+	}
+
+	impl pallet_parachain_template::runtime_api::StudentRegistryApi<Block, AccountId, BlockNumber, Hash>
+		for Runtime
+	{
+		fn alumnus(
+			student_id: u32,
+		) -> Option<pallet_parachain_template::AlumniRecord<AccountId, BlockNumber, Hash>> {
+			TemplatePallet::alumnus(student_id)
+		}
+
+		fn student_timeline(
+			student_id: u32,
+		) -> Vec<pallet_parachain_template::HistoryEntry<Hash>> {
+			TemplatePallet::student_timeline(student_id)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -23,13 +23,14 @@
 //
 // For more information, please refer to <http://unlicense.org>
 
-mod xcm_config;
+pub mod xcm_config;
 
 use polkadot_sdk::{staging_parachain_info as parachain_info, staging_xcm as xcm, *};
 #[cfg(not(feature = "runtime-benchmarks"))]
 use polkadot_sdk::{staging_xcm_builder as xcm_builder, staging_xcm_executor as xcm_executor};
 
 // Substrate and Polkadot dependencies
+use codec::MaxEncodedLen;
 use cumulus_pallet_parachain_system::RelayNumberMonotonicallyIncreases;
 use cumulus_primitives_core::{AggregateMessageOrigin, ParaId};
 use frame_support::{
@@ -37,37 +38,48 @@ use frame_support::{
 	dispatch::DispatchClass,
 	parameter_types,
 	traits::{
-		ConstBool, ConstU32, ConstU64, ConstU8, EitherOfDiverse, TransformOrigin, VariantCountOf,
+		tokens::nonfungibles_v2, AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64,
+		ConstU8, Contains, EitherOfDiverse, Everything, PalletInfoAccess, TransformOrigin,
+		VariantCountOf,
 	},
 	weights::{ConstantMultiplier, Weight},
 	PalletId,
 };
 use frame_system::{
 	limits::{BlockLength, BlockWeights},
-	EnsureRoot,
+	EnsureNever, EnsureRoot,
 };
+use pallet_nfts::{CollectionConfig, CollectionSettings, ItemConfig, MintSettings, PalletFeatures};
 use pallet_xcm::{EnsureXcm, IsVoiceOfBody};
 use parachains_common::message_queue::{NarrowOriginToSibling, ParaIdToSibling};
 use polkadot_runtime_common::{
 	xcm_sender::NoPriceForMessageDelivery, BlockHashCount, SlowAdjustingFeeUpdate,
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_runtime::Perbill;
+use sp_runtime::{
+	traits::{AccountIdConversion, Verify},
+	DispatchResult, Perbill,
+};
 use sp_version::RuntimeVersion;
-use xcm::latest::prelude::BodyId;
+use xcm::latest::prelude::{Asset, BodyId, GeneralIndex, Index, Location, PalletInstance};
 
 use crate::configs::xcm_config::XcmRouter;
+use polkadot_sdk::staging_xcm_builder::AliasesIntoAccountId32;
 
 // Local module imports
 use super::{
 	weights::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight},
 	AccountId, Aura, Balance, Balances, Block, BlockNumber, CollatorSelection, ConsensusHook, Hash,
-	MessageQueue, Nonce, PalletInfo, ParachainSystem, Runtime, RuntimeCall, RuntimeEvent,
+	MessageQueue, Nfts, Nonce, PalletInfo, ParachainSystem, Runtime, RuntimeCall, RuntimeEvent,
 	RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask, Session, SessionKeys,
-	System, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, EXISTENTIAL_DEPOSIT, HOURS,
-	MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, NORMAL_DISPATCH_RATIO, SLOT_DURATION, VERSION,
+	Signature, System, Timestamp, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, DAYS,
+	EXISTENTIAL_DEPOSIT, HOURS, MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, NORMAL_DISPATCH_RATIO,
+	SLOT_DURATION, VERSION,
+};
+use xcm_config::{
+	LocationToAccountId, PolkadotXcmQueries, RelayLocation, RelayNetwork,
+	SiblingParachainLocations, UniversalLocation, XcmOriginToTransactDispatchOrigin,
 };
-use xcm_config::{RelayLocation, XcmOriginToTransactDispatchOrigin};
 
 parameter_types! {
 	pub const Version: RuntimeVersion = VERSION;
@@ -129,6 +141,8 @@ impl frame_system::Config for Runtime {
 	/// The action to take on a Runtime Upgrade
 	type OnSetCode = cumulus_pallet_parachain_system::ParachainSetCode<Self>;
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	/// Everything but the calls of `pallet_nfts`.
+	type BaseCallFilter = NoNftsCalls;
 }
 
 /// Configure the palelt weight reclaim tx.
@@ -193,6 +207,37 @@ impl pallet_sudo::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+	pub MaxProposalWeight: Weight = Perbill::from_percent(50) * RuntimeBlockWeights::get().max_block;
+}
+
+pub type CouncilCollective = pallet_collective::Instance1;
+
+/// Origin administering the chain: root, or two thirds of the council.
+pub type AdminOrigin = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
+>;
+
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Proposal = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+	type SetMembersOrigin = AdminOrigin;
+	type MaxProposalWeight = MaxProposalWeight;
+	type DisapproveOrigin = AdminOrigin;
+	type KillOrigin = AdminOrigin;
+	type Consideration = ();
+}
+
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
 	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
@@ -298,19 +343,62 @@ parameter_types! {
 
 parameter_types! {
 	// Max bytes allowed for student's name
-	pub const MaxNameLen: u32 = 10;
+	pub const MaxNameLen: u32 = 64;
 	// Max bytes allowed for student's surname
-	pub const MaxSurnameLen: u32 = 20;
+	pub const MaxSurnameLen: u32 = 64;
+	// Max bytes allowed for the off-chain reference of committed personal data
+	pub const MaxReferenceLen: u32 = 128;
+	// Oldest accepted date of birth, in years before today
+	pub const MaxAge: u32 = 150;
+	// Changes kept in each student's history
+	pub const MaxHistoryLen: u32 = 32;
 
 	// Destination parachain for graduated students
 	pub const GraduationDestinationPara: u32 = 2000;
+	// Institution receiving them there, the first one registered at genesis
+	pub const GraduationDestinationInstitution: u32 = 0;
+	// Blocks a student has to approve a graduation transfer
+	pub const TransferApprovalPeriod: BlockNumber = 7 * DAYS;
+	// Blocks a deleted student can be restored before it is purged
+	pub const DeletionGracePeriod: BlockNumber = 30 * DAYS;
+	// Students that can be deleted in the same block
+	pub const MaxDeletionsPerBlock: u32 = 64;
+	// Students a single account can own
+	pub const MaxStudentsPerOwner: u32 = 10_000;
+	// Bytes of an institution's name
+	pub const MaxInstitutionNameLen: u32 = 128;
+	// Admins of a single institution
+	pub const MaxInstitutionAdmins: u32 = 16;
+	// Bytes of a job title or department
+	pub const MaxPositionLen: u32 = 64;
+	// Job offers a student can have open at once
+	pub const MaxJobOffers: u32 = 16;
+	// Of those, job offers from a single parachain
+	pub const MaxJobOffersPerPara: u32 = 4;
+	// Blocks to wait for the source chain to answer a verification request
+	pub const VerificationTimeout: BlockNumber = HOURS;
+	// Unanswered verification requests of a student
+	pub const MaxPendingVerifications: u32 = 4;
+	// Verification requests timing out in the same block
+	pub const MaxVerificationsPerBlock: u32 = 64;
+	// Accounts of the institutions, collecting tuition and holding their scholarship funds
+	pub const ScholarshipPalletId: PalletId = PalletId(*b"py/schlr");
+	// Blocks between two payments of a stipend
+	pub const StipendPeriod: BlockNumber = 30 * DAYS;
+	// Stipends paid in the same block
+	pub const MaxStipendsPerBlock: u32 = 64;
+	// Stipends of graduates on siblings are sent like any other XCM
+	pub StipendSendWeight: Weight =
+		<<Runtime as pallet_xcm::Config>::WeightInfo as pallet_xcm::WeightInfo>::send();
+	pub const MaxInvoicesPerStudent: u32 = 16;
+	pub const MaxCommitteeMembers: u32 = 16;
+	pub const GraduationApprovalPeriod: BlockNumber = 14 * DAYS;
 }
 
-/// We allow root and the StakingAdmin to execute privileged collator selection operations.
-pub type CollatorSelectionUpdateOrigin = EitherOfDiverse<
-	EnsureRoot<AccountId>,
-	EnsureXcm<IsVoiceOfBody<RelayLocation, StakingAdminBodyId>>,
->;
+/// We allow root, the council and the StakingAdmin to execute privileged collator selection
+/// operations.
+pub type CollatorSelectionUpdateOrigin =
+	EitherOfDiverse<AdminOrigin, EnsureXcm<IsVoiceOfBody<RelayLocation, StakingAdminBodyId>>>;
 
 impl pallet_collator_selection::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -328,6 +416,162 @@ impl pallet_collator_selection::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	// Owner of the collections holding the students of each institution
+	pub const StudentItemsPalletId: PalletId = PalletId(*b"py/stdnt");
+	// Item metadata holds the encoded record of a student
+	pub StudentItemMetadataLimit: u32 =
+		pallet_parachain_template::Student::<Runtime>::max_encoded_len() as u32;
+	pub NftsFeatures: PalletFeatures = PalletFeatures::all_enabled();
+}
+
+/// `pallet_nfts` only holds student items, which change through the template pallet alone, so
+/// its calls are filtered out. Root can still dispatch them.
+pub struct NoNftsCalls;
+impl Contains<RuntimeCall> for NoNftsCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		!matches!(call, RuntimeCall::Nfts(_))
+	}
+}
+
+impl pallet_nfts::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type CollectionId = u32;
+	type ItemId = u32;
+	type Currency = Balances;
+	type ForceOrigin = AdminOrigin;
+	// Collections are created with the institutions only
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureNever<AccountId>>;
+	type Locker = ();
+	type CollectionDeposit = ConstU128<0>;
+	type ItemDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type AttributeDepositBase = ConstU128<0>;
+	type DepositPerByte = ConstU128<0>;
+	type StringLimit = StudentItemMetadataLimit;
+	type KeyLimit = ConstU32<64>;
+	type ValueLimit = ConstU32<256>;
+	type ApprovalsLimit = ConstU32<20>;
+	type ItemAttributesApprovalsLimit = ConstU32<30>;
+	type MaxTips = ConstU32<10>;
+	type MaxDeadlineDuration = ConstU32<{ 30 * DAYS }>;
+	type MaxAttributesPerCall = ConstU32<10>;
+	type Features = NftsFeatures;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as Verify>::Signer;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = ();
+	type WeightInfo = pallet_nfts::weights::SubstrateWeight<Runtime>;
+	type BlockNumberProvider = System;
+}
+
+/// Students of the template pallet as `pallet_nfts` items. The collection of an institution has
+/// the id of the institution and is owned by [`StudentItemsPalletId`].
+pub struct NftStudentItems;
+impl pallet_parachain_template::StudentItems<AccountId> for NftStudentItems {
+	fn create_collection(institution_id: u32) -> DispatchResult {
+		let owner = StudentItemsPalletId::get().into_account_truncating();
+		let config = CollectionConfig {
+			settings: CollectionSettings::all_enabled(),
+			max_supply: None,
+			mint_settings: MintSettings::default(),
+		};
+		<Nfts as nonfungibles_v2::Create<_, _>>::create_collection_with_id(
+			institution_id,
+			&owner,
+			&owner,
+			&config,
+		)
+	}
+
+	fn mint(
+		institution_id: u32,
+		student_id: u32,
+		owner: &AccountId,
+		metadata: &[u8],
+	) -> DispatchResult {
+		<Nfts as nonfungibles_v2::Mutate<_, _>>::mint_into(
+			&institution_id,
+			&student_id,
+			owner,
+			&ItemConfig::default(),
+			true,
+		)?;
+		Self::set_metadata(institution_id, student_id, metadata)
+	}
+
+	fn set_metadata(institution_id: u32, student_id: u32, metadata: &[u8]) -> DispatchResult {
+		<Nfts as nonfungibles_v2::Mutate<_, _>>::set_item_metadata(
+			None,
+			&institution_id,
+			&student_id,
+			metadata,
+		)
+	}
+
+	fn transfer(institution_id: u32, student_id: u32, to: &AccountId) -> DispatchResult {
+		<Nfts as nonfungibles_v2::Transfer<_>>::transfer(&institution_id, &student_id, to)
+	}
+
+	fn burn(institution_id: u32, student_id: u32) -> DispatchResult {
+		<Nfts as nonfungibles_v2::Mutate<_, _>>::burn(&institution_id, &student_id, None)
+	}
+
+	fn owner(institution_id: u32, student_id: u32) -> Option<AccountId> {
+		<Nfts as nonfungibles_v2::Inspect<_>>::owner(&institution_id, &student_id)
+	}
+
+	fn asset(institution_id: u32, student_id: u32) -> Asset {
+		let collection = Location::new(
+			0,
+			[PalletInstance(Nfts::index() as u8), GeneralIndex(institution_id.into())],
+		);
+		(collection, Index(student_id.into())).into()
+	}
+}
+
+/// Instance of `pallet_assets` holding the tokens of sibling parachains, keyed by their location.
+pub type ForeignAssetsInstance = pallet_assets::Instance1;
+
+impl pallet_assets::Config<ForeignAssetsInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type AssetId = Location;
+	type AssetIdParameter = Location;
+	type Currency = Balances;
+	// Foreign assets are registered by governance, deposits are not needed
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureNever<AccountId>>;
+	type ForceOrigin = AdminOrigin;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Holder = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = xcm_config::ForeignAssetsBenchmarkHelper;
+}
+
+/// Rates of the tokens of sibling parachains to the native token, at which they pay for XCM
+/// execution.
+impl pallet_asset_rate::Config for Runtime {
+	type WeightInfo = pallet_asset_rate::weights::SubstrateWeight<Runtime>;
+	type RuntimeEvent = RuntimeEvent;
+	type CreateOrigin = AdminOrigin;
+	type RemoveOrigin = AdminOrigin;
+	type UpdateOrigin = AdminOrigin;
+	type Currency = Balances;
+	type AssetKind = Location;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = xcm_config::ForeignAssetsBenchmarkHelper;
+}
+
 /// Configure the pallet template in pallets/template.
 impl pallet_parachain_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -336,10 +580,55 @@ impl pallet_parachain_template::Config for Runtime {
 	// New associated types required by the pallet's Config trait
 	type MaxNameLen = MaxNameLen;
 	type MaxSurnameLen = MaxSurnameLen;
+	type MaxReferenceLen = MaxReferenceLen;
+	type MaxAge = MaxAge;
+	type MaxHistoryLen = MaxHistoryLen;
+	type UnixTime = Timestamp;
 
 	type XcmSender = XcmRouter;
 
+	// Calls sent by the pallet on sibling chains arrive as `OriginKind::Xcm`. Accounts on a
+	// sibling can reach the same origin by paying for execution, the pallet tells them apart.
+	type XcmOrigin = EnsureXcm<SiblingParachainLocations>;
+
+	// Graduations descend into the owner's `AccountId32` junction on the destination
+	type AccountIdToLocation = AliasesIntoAccountId32<RelayNetwork, AccountId>;
+	// Same conversion as the XCM executor, so beneficiaries validated here map on the destination
+	type LocationToAccountId = LocationToAccountId;
+
 	type RuntimeCall = RuntimeCall;
 
 	type GraduationDestinationPara = GraduationDestinationPara;
+	type GraduationDestinationInstitution = GraduationDestinationInstitution;
+	type TransferApprovalPeriod = TransferApprovalPeriod;
+	type DeletionGracePeriod = DeletionGracePeriod;
+	type MaxDeletionsPerBlock = MaxDeletionsPerBlock;
+	type MaxStudentsPerOwner = MaxStudentsPerOwner;
+
+	type InstitutionRegistrar = AdminOrigin;
+	type MaxInstitutionNameLen = MaxInstitutionNameLen;
+	type MaxInstitutionAdmins = MaxInstitutionAdmins;
+	type MaxPositionLen = MaxPositionLen;
+	type MaxJobOffers = MaxJobOffers;
+	type MaxJobOffersPerPara = MaxJobOffersPerPara;
+
+	// Verification answers are query responses handled by `PolkadotXcm`
+	type Queries = PolkadotXcmQueries;
+	type ResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+	type UniversalLocation = UniversalLocation;
+	type VerificationTimeout = VerificationTimeout;
+	type MaxPendingVerifications = MaxPendingVerifications;
+	type MaxVerificationsPerBlock = MaxVerificationsPerBlock;
+
+	type Items = NftStudentItems;
+
+	// Stipends are paid in the native token, reserve transferred to graduates on siblings
+	type Currency = Balances;
+	type PalletId = ScholarshipPalletId;
+	type StipendPeriod = StipendPeriod;
+	type MaxStipendsPerBlock = MaxStipendsPerBlock;
+	type StipendSendWeight = StipendSendWeight;
+	type MaxInvoicesPerStudent = MaxInvoicesPerStudent;
+	type MaxCommitteeMembers = MaxCommitteeMembers;
+	type GraduationApprovalPeriod = GraduationApprovalPeriod;
 }
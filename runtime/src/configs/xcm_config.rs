@@ -1,38 +1,55 @@
 use crate::{
-	AccountId, AllPalletsWithSystem, Balances, ParachainInfo, ParachainSystem, PolkadotXcm,
-	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, WeightToFee, XcmpQueue,
+	configs::{ForeignAssetsInstance, NftStudentItems},
+	AccountId, AllPalletsWithSystem, AssetRate, Balance, Balances, BlockNumber, CollatorSelection,
+	ForeignAssets, Nfts, ParachainInfo, ParachainSystem, PolkadotXcm, Runtime, RuntimeCall,
+	RuntimeEvent, RuntimeOrigin, TemplatePallet, WeightToFee, XcmpQueue,
 };
 
+use alloc::vec::Vec;
 use polkadot_sdk::{
 	staging_xcm as xcm, staging_xcm_builder as xcm_builder, staging_xcm_executor as xcm_executor, *,
 };
 
+use cumulus_primitives_utility::{TakeFirstAssetTrader, XcmFeesTo32ByteAccount};
 use frame_support::{
 	parameter_types,
-	traits::{ConstU32, Contains, Everything, Nothing},
+	traits::{
+		tokens::{nonfungibles, nonfungibles_v2},
+		ConstU32, Contains, ContainsPair, Equals, Everything, Nothing, PalletInfoAccess,
+	},
 	weights::Weight,
 };
 use frame_system::EnsureRoot;
+use pallet_parachain_template::StudentItems;
 use pallet_xcm::XcmPassthrough;
+use parachains_common::xcm_config::AssetFeeAsExistentialDepositMultiplier;
 use polkadot_parachain_primitives::primitives::Sibling;
 use polkadot_runtime_common::impls::ToAuthor;
 use polkadot_sdk::{
 	polkadot_sdk_frame::traits::Disabled,
 	staging_xcm_builder::{DenyRecursively, DenyThenTry},
 };
+use sp_runtime::{traits::Identity, DispatchResult, TokenError};
 use xcm::latest::prelude::*;
 use xcm_builder::{
-	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowTopLevelPaidExecutionFrom,
-	DenyReserveTransferToRelayChain, EnsureXcmOrigin, FixedWeightBounds,
-	FrameTransactionalProcessor, FungibleAdapter, IsConcrete, NativeAsset, ParentIsPreset,
+	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses,
+	AllowTopLevelPaidExecutionFrom, DenyReserveTransferToRelayChain, DescribeAccountKey20Terminal,
+	DescribeAllTerminal, DescribeFamily, EnsureXcmOrigin, FixedWeightBounds,
+	FrameTransactionalProcessor, FungibleAdapter, FungiblesAdapter, HashedDescription, IsConcrete,
+	MatchedConvertedConcreteId, NativeAsset, NoChecking, NonFungiblesAdapter, ParentIsPreset,
 	RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
 	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
 	TrailingSetTopicAsId, UsingComponents, WithComputedOrigin, WithUniqueTopic,
 };
-use xcm_executor::XcmExecutor;
+use xcm_executor::{
+	traits::{Error as MatchError, JustTry, MatchesNonFungibles},
+	XcmExecutor,
+};
 
 parameter_types! {
 	pub const RelayLocation: Location = Location::parent();
+	pub const HereLocation: Location = Location::here();
+	pub CheckingAccount: AccountId = PolkadotXcm::check_account();
 	pub const RelayNetwork: Option<NetworkId> = None;
 	pub RelayChainOrigin: RuntimeOrigin = cumulus_pallet_xcm::Origin::Relay.into();
 	// For the real deployment, it is recommended to set `RelayNetwork` according to the relay chain
@@ -50,14 +67,22 @@ pub type LocationToAccountId = (
 	SiblingParachainConvertsVia<Sibling, AccountId>,
 	// Straight up local `AccountId32` origins just alias directly to `AccountId`.
 	AccountId32Aliases<RelayNetwork, AccountId>,
+	// Foreign locations alias into accounts according to a hash of their standard description.
+	// This is how a signer on a sibling parachain (`DescendOrigin` into its `AccountId32`) gets
+	// a local account of its own.
+	HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>,
+	// Local `AccountKey20` beneficiaries alias into the hash of their key, since this chain only
+	// has 32-byte accounts.
+	HashedDescription<AccountId, DescribeAccountKey20Terminal>,
 );
 
-/// Means for transacting assets on this chain.
-pub type LocalAssetTransactor = FungibleAdapter<
+/// Means for transacting the native currency on this chain. The token of the relay chain is a
+/// foreign asset, see [`ForeignFungiblesTransactor`].
+pub type FungibleTransactor = FungibleAdapter<
 	// Use this currency:
 	Balances,
 	// Use this currency when it is a fungible asset matching the given location or name:
-	IsConcrete<RelayLocation>,
+	IsConcrete<HereLocation>,
 	// Do a simple punn to convert an AccountId32 Location into a native chain account ID:
 	LocationToAccountId,
 	// Our chain's account ID type (we can't get away without mentioning it explicitly):
@@ -66,6 +91,121 @@ pub type LocalAssetTransactor = FungibleAdapter<
 	(),
 >;
 
+/// The relay chain and sibling parachains, and anything below the latter: the reserves of the
+/// tokens held in `ForeignAssets`.
+pub type ForeignAssetLocations = (Equals<RelayLocation>, SiblingParachainLocations);
+
+/// Matches the tokens of the relay chain and of sibling parachains onto their location as an
+/// asset id of `ForeignAssets`.
+pub type ForeignAssetsConvertedConcreteId =
+	MatchedConvertedConcreteId<Location, Balance, ForeignAssetLocations, Identity, JustTry>;
+
+/// Sibling tokens as asset ids of `ForeignAssets` in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ForeignAssetsBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_assets::BenchmarkHelper<Location> for ForeignAssetsBenchmarkHelper {
+	fn create_asset_id_parameter(id: u32) -> Location {
+		Location::new(1, [Parachain(id)])
+	}
+}
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_asset_rate::AssetKindFactory<Location> for ForeignAssetsBenchmarkHelper {
+	fn create_asset_kind(seed: u32) -> Location {
+		Location::new(1, [Parachain(seed)])
+	}
+}
+
+/// Means for transacting the tokens of the relay chain and of sibling parachains registered in
+/// `ForeignAssets`.
+pub type ForeignFungiblesTransactor = FungiblesAdapter<
+	ForeignAssets,
+	ForeignAssetsConvertedConcreteId,
+	LocationToAccountId,
+	AccountId,
+	// The relay chain and the siblings are the reserves of their tokens, there are no teleports
+	// to track.
+	NoChecking,
+	CheckingAccount,
+>;
+
+/// `pallet_nfts` as seen by the `NonFungiblesAdapter`, which works with the first version of the
+/// nonfungibles traits. Student items only come in, as copies of the students received from a
+/// sibling; they leave with graduations of the template pallet.
+pub struct StudentItemsAsNonFungibles;
+impl nonfungibles::Inspect<AccountId> for StudentItemsAsNonFungibles {
+	type ItemId = u32;
+	type CollectionId = u32;
+
+	fn owner(collection: &u32, item: &u32) -> Option<AccountId> {
+		<Nfts as nonfungibles_v2::Inspect<_>>::owner(collection, item)
+	}
+}
+
+impl nonfungibles::Mutate<AccountId> for StudentItemsAsNonFungibles {
+	fn mint_into(collection: &u32, item: &u32, who: &AccountId) -> DispatchResult {
+		let metadata = TemplatePallet::item_metadata(*item).ok_or(TokenError::UnknownAsset)?;
+		NftStudentItems::mint(*collection, *item, who, &metadata)
+	}
+}
+
+impl nonfungibles::Transfer<AccountId> for StudentItemsAsNonFungibles {
+	fn transfer(_collection: &u32, _item: &u32, _destination: &AccountId) -> DispatchResult {
+		Err(TokenError::Unsupported.into())
+	}
+}
+
+/// Matches the student items of this chain, and the ones reserved by a sibling onto the copy
+/// belonging to the student received with them.
+pub struct StudentItemsMatcher;
+impl MatchesNonFungibles<u32, u32> for StudentItemsMatcher {
+	fn matches_nonfungibles(asset: &Asset) -> Result<(u32, u32), MatchError> {
+		let NonFungible(Index(item)) = asset.fun else { return Err(MatchError::AssetNotHandled) };
+		let item = u32::try_from(item).map_err(|_| MatchError::InstanceConversionFailed)?;
+		match asset.id.0.unpack() {
+			(0, [PalletInstance(pallet), GeneralIndex(collection)])
+				if *pallet as usize == Nfts::index() =>
+				Ok((
+					u32::try_from(*collection).map_err(|_| MatchError::AssetIdConversionFailed)?,
+					item,
+				)),
+			(1, [Parachain(para_id), PalletInstance(_), GeneralIndex(_)]) =>
+				TemplatePallet::received_item(*para_id, item).ok_or(MatchError::AssetNotHandled),
+			_ => Err(MatchError::AssetNotHandled),
+		}
+	}
+}
+
+/// Means for transacting student items on this chain.
+pub type StudentItemsTransactor = NonFungiblesAdapter<
+	StudentItemsAsNonFungibles,
+	StudentItemsMatcher,
+	LocationToAccountId,
+	AccountId,
+	// Sibling items are reserve based, there are no teleports to track.
+	NoChecking,
+	(),
+>;
+
+/// Means for transacting assets on this chain.
+pub type LocalAssetTransactor =
+	(FungibleTransactor, ForeignFungiblesTransactor, StudentItemsTransactor);
+
+/// Student items sent by the sibling parachain they belong to, which keeps them as the reserve.
+pub struct StudentItemsFromSiblings;
+impl ContainsPair<Asset, Location> for StudentItemsFromSiblings {
+	fn contains(asset: &Asset, origin: &Location) -> bool {
+		matches!(
+			(&asset.fun, asset.id.0.unpack(), origin.unpack()),
+			(
+				NonFungible(_),
+				(1, [Parachain(item_para), PalletInstance(_), GeneralIndex(_)]),
+				(1, [Parachain(origin_para)]),
+			) if item_para == origin_para
+		)
+	}
+}
+
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
 /// biases the kind of local `Origin` it will become.
@@ -109,12 +249,61 @@ impl Contains<Location> for AllowSiblingParachains {
 	}
 }
 
+/// Notify queries of `PolkadotXcm`, for the template pallet's verification requests
+pub struct PolkadotXcmQueries;
+impl pallet_parachain_template::NotifyQuery<RuntimeCall, BlockNumber> for PolkadotXcmQueries {
+	fn new_notify_query(responder: Location, notify: RuntimeCall, timeout: BlockNumber) -> QueryId {
+		PolkadotXcm::new_notify_query(responder, notify, timeout, Here)
+	}
+}
+
+/// Sibling parachains and any location below them, e.g. their accounts
+pub struct SiblingParachainLocations;
+impl Contains<Location> for SiblingParachainLocations {
+	fn contains(location: &Location) -> bool {
+		matches!(location.unpack(), (1, [Parachain(_), ..]))
+	}
+}
+
+/// Assets which can be reserve transferred: the native token, which this chain is the reserve of,
+/// and the tokens of sibling parachains, which are the reserves of theirs.
+pub struct NativeAndSiblingTokens;
+impl Contains<(Location, Vec<Asset>)> for NativeAndSiblingTokens {
+	fn contains((_, assets): &(Location, Vec<Asset>)) -> bool {
+		assets.iter().all(|asset| {
+			matches!(asset.fun, Fungible(_)) &&
+				matches!(asset.id.0.unpack(), (0, []) | (1, [Parachain(_)]))
+		})
+	}
+}
+
+parameter_types! {
+	// Fees paid with the tokens of siblings go to the collators, as do the native ones.
+	pub StakingPot: Option<AccountId> = Some(CollatorSelection::account_id());
+}
+
+/// Fee in the tokens of siblings registered in `ForeignAssets`: the native one, converted at the
+/// rate of the asset in `AssetRate`. Assets without a rate can't pay for execution.
+pub type ForeignAssetsFeeCharger =
+	AssetFeeAsExistentialDepositMultiplier<Runtime, WeightToFee, AssetRate, ForeignAssetsInstance>;
+
+/// Buys execution with the tokens of siblings registered in `ForeignAssets` at their rate.
+pub type ForeignAssetsTrader = TakeFirstAssetTrader<
+	AccountId,
+	ForeignAssetsFeeCharger,
+	ForeignAssetsConvertedConcreteId,
+	ForeignAssets,
+	XcmFeesTo32ByteAccount<ForeignFungiblesTransactor, AccountId, StakingPot>,
+>;
+
 
 pub type Barrier = TrailingSetTopicAsId<
 	DenyThenTry<
 		DenyRecursively<DenyReserveTransferToRelayChain>,
 		(
 			TakeWeightCredit,
+			// Expected query responses, e.g. to verification requests
+			AllowKnownQueryResponses<PolkadotXcm>,
 			WithComputedOrigin<
 				(
 					AllowTopLevelPaidExecutionFrom<Everything>,
@@ -151,13 +340,16 @@ impl xcm_executor::Config for XcmConfig {
 	// How to withdraw and deposit an asset.
 	type AssetTransactor = LocalAssetTransactor;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
-	type IsReserve = NativeAsset;
+	// Siblings are the reserves of their own tokens and student items.
+	type IsReserve = (NativeAsset, StudentItemsFromSiblings);
 	type IsTeleporter = (); // Teleporting is disabled.
 	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
-	type Trader =
-		UsingComponents<WeightToFee, RelayLocation, AccountId, Balances, ToAuthor<Runtime>>;
+	type Trader = (
+		UsingComponents<WeightToFee, HereLocation, AccountId, Balances, ToAuthor<Runtime>>,
+		ForeignAssetsTrader,
+	);
 	type ResponseHandler = PolkadotXcm;
 	type AssetTrap = PolkadotXcm;
 	type AssetClaims = PolkadotXcm;
@@ -201,8 +393,9 @@ impl pallet_xcm::Config for Runtime {
 	// ^ Disable dispatchable execute on the XCM pallet.
 	// Needs to be `Everything` for local testing.
 	type XcmExecutor = XcmExecutor<XcmConfig>;
-	type XcmTeleportFilter = Everything;
-	type XcmReserveTransferFilter = Nothing;
+	// Teleporting is disabled, tokens move between chains through their reserves.
+	type XcmTeleportFilter = Nothing;
+	type XcmReserveTransferFilter = NativeAndSiblingTokens;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
 	type UniversalLocation = UniversalLocation;
 	type RuntimeOrigin = RuntimeOrigin;
@@ -1,6 +1,7 @@
 use crate::{
-	AccountId, BalancesConfig, CollatorSelectionConfig, ParachainInfoConfig, PolkadotXcmConfig,
-	RuntimeGenesisConfig, SessionConfig, SessionKeys, SudoConfig, EXISTENTIAL_DEPOSIT,
+	AccountId, BalancesConfig, CollatorSelectionConfig, CouncilConfig, ParachainInfoConfig,
+	PolkadotXcmConfig, RuntimeGenesisConfig, SessionConfig, SessionKeys, SudoConfig,
+	TemplatePalletConfig, EXISTENTIAL_DEPOSIT,
 };
 
 use alloc::{vec, vec::Vec};
@@ -19,6 +20,8 @@ const SAFE_XCM_VERSION: u32 = xcm::prelude::XCM_VERSION;
 /// Parachain id used for genesis config presets of parachain template.
 #[docify::export_content]
 pub const PARACHAIN_ID: u32 = 1000;
+/// Preset of a chain administered by its council, without a sudo key.
+pub const COUNCIL_RUNTIME_PRESET: &str = "council";
 
 /// Generate the session keys from individual elements.
 ///
@@ -30,9 +33,14 @@ pub fn template_session_keys(keys: AuraId) -> SessionKeys {
 fn testnet_genesis(
 	invulnerables: Vec<(AccountId, AuraId)>,
 	endowed_accounts: Vec<AccountId>,
-	root: AccountId,
+	root: Option<AccountId>,
+	council: Vec<AccountId>,
 	id: ParaId,
 ) -> Value {
+	// A first accredited institution, administered by the root account if there is one. Without
+	// it the council appoints the admins by motion
+	let institution_admins = root.iter().cloned().collect::<Vec<_>>();
+
 	build_struct_json_patch!(RuntimeGenesisConfig {
 		balances: BalancesConfig {
 			balances: endowed_accounts
@@ -59,7 +67,11 @@ fn testnet_genesis(
 				.collect::<Vec<_>>(),
 		},
 		polkadot_xcm: PolkadotXcmConfig { safe_xcm_version: Some(SAFE_XCM_VERSION) },
-		sudo: SudoConfig { key: Some(root) },
+		template_pallet: TemplatePalletConfig {
+			institutions: vec![(b"Polkadot University".to_vec(), institution_admins)],
+		},
+		council: CouncilConfig { members: council },
+		sudo: SudoConfig { key: root },
 	})
 }
 
@@ -71,7 +83,8 @@ fn local_testnet_genesis() -> Value {
 			(Sr25519Keyring::Bob.to_account_id(), Sr25519Keyring::Bob.public().into()),
 		],
 		Sr25519Keyring::well_known().map(|k| k.to_account_id()).collect(),
-		Sr25519Keyring::Alice.to_account_id(),
+		Some(Sr25519Keyring::Alice.to_account_id()),
+		initial_council(),
 		PARACHAIN_ID.into(),
 	)
 }
@@ -84,16 +97,39 @@ fn development_config_genesis() -> Value {
 			(Sr25519Keyring::Bob.to_account_id(), Sr25519Keyring::Bob.public().into()),
 		],
 		Sr25519Keyring::well_known().map(|k| k.to_account_id()).collect(),
-		Sr25519Keyring::Alice.to_account_id(),
+		Some(Sr25519Keyring::Alice.to_account_id()),
+		initial_council(),
 		PARACHAIN_ID.into(),
 	)
 }
 
+fn council_genesis() -> Value {
+	testnet_genesis(
+		// initial collators.
+		vec![
+			(Sr25519Keyring::Alice.to_account_id(), Sr25519Keyring::Alice.public().into()),
+			(Sr25519Keyring::Bob.to_account_id(), Sr25519Keyring::Bob.public().into()),
+		],
+		Sr25519Keyring::well_known().map(|k| k.to_account_id()).collect(),
+		None,
+		initial_council(),
+		PARACHAIN_ID.into(),
+	)
+}
+
+/// Council members at genesis, administering the chain through two-thirds motions.
+fn initial_council() -> Vec<AccountId> {
+	[Sr25519Keyring::Alice, Sr25519Keyring::Bob, Sr25519Keyring::Charlie]
+		.map(|k| k.to_account_id())
+		.into()
+}
+
 /// Provides the JSON representation of predefined genesis config for given `id`.
 pub fn get_preset(id: &PresetId) -> Option<vec::Vec<u8>> {
 	let patch = match id.as_ref() {
 		sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET => local_testnet_genesis(),
 		sp_genesis_builder::DEV_RUNTIME_PRESET => development_config_genesis(),
+		COUNCIL_RUNTIME_PRESET => council_genesis(),
 		_ => return None,
 	};
 	Some(
@@ -108,5 +144,6 @@ pub fn preset_names() -> Vec<PresetId> {
 	vec![
 		PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
 		PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+		PresetId::from(COUNCIL_RUNTIME_PRESET),
 	]
 }
@@ -0,0 +1,532 @@
+//! Tests of the runtime configuration across two chains of this runtime, e.g. the University
+//! (para 1000) and the Company (para 2000): reserve transfers and graduations, executed from the
+//! outbound queue of the sender, and the council that administers a chain without sudo.
+
+use crate::{
+	configs::{xcm_config::XcmConfig, CouncilCollective},
+	genesis_config_presets, AccountId, AssetRate, Balance, Balances, BalancesConfig, Council,
+	ForeignAssets, ForeignAssetsConfig, ParachainInfoConfig, ParachainSystem, PolkadotXcm,
+	PolkadotXcmConfig, Runtime, RuntimeCall, RuntimeEvent, RuntimeGenesisConfig, RuntimeOrigin,
+	Sudo, System, TemplatePallet, XcmpQueue, COUNCIL_RUNTIME_PRESET, UNIT,
+};
+use codec::{Decode, Encode};
+use polkadot_sdk::{staging_xcm as xcm, staging_xcm_executor as xcm_executor, *};
+
+use cumulus_primitives_core::{XcmpMessageFormat, XcmpMessageSource};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::GetDispatchInfo,
+	genesis_builder_helper::build_state,
+	traits::{fungible::Inspect as _, fungibles::Inspect as _},
+	weights::Weight,
+};
+use serde_json::Value;
+use sp_keyring::Sr25519Keyring;
+use sp_runtime::{
+	traits::{AccountIdConversion, Hash as _},
+	BuildStorage, DispatchError, FixedU128,
+};
+use xcm::{latest::prelude::*, VersionedXcm};
+use xcm_executor::XcmExecutor;
+
+const UNIVERSITY: u32 = 1000;
+const COMPANY: u32 = 2000;
+const ALICE: AccountId = AccountId::new([1; 32]);
+const BOB: AccountId = AccountId::new([2; 32]);
+const INITIAL_BALANCE: Balance = 1_000 * UNIT;
+
+fn sibling(para_id: u32) -> Location {
+	Location::new(1, [Parachain(para_id)])
+}
+
+fn sovereign_account(para_id: u32) -> AccountId {
+	polkadot_parachain_primitives::primitives::Sibling::from(para_id).into_account_truncating()
+}
+
+/// A chain with `para_id` where Alice has native tokens, the tokens of `sibling_id` are registered
+/// with a rate at par and there is an open channel to it.
+fn new_chain(para_id: u32, sibling_id: u32) -> sp_io::TestExternalities {
+	let storage = RuntimeGenesisConfig {
+		balances: BalancesConfig { balances: vec![(ALICE, INITIAL_BALANCE)], ..Default::default() },
+		parachain_info: ParachainInfoConfig { parachain_id: para_id.into(), ..Default::default() },
+		polkadot_xcm: PolkadotXcmConfig {
+			safe_xcm_version: Some(xcm::latest::VERSION),
+			..Default::default()
+		},
+		foreign_assets: ForeignAssetsConfig {
+			assets: vec![(sibling(sibling_id), ALICE, true, 1)],
+			..Default::default()
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AssetRate::create(
+			RuntimeOrigin::root(),
+			Box::new(sibling(sibling_id)),
+			FixedU128::from_u32(1)
+		));
+		ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(sibling_id.into());
+	});
+	ext
+}
+
+/// Executes the message sent by the chain `sender_id` on `receiver`.
+fn deliver(
+	sender: &mut sp_io::TestExternalities,
+	sender_id: u32,
+	receiver: &mut sp_io::TestExternalities,
+) -> Outcome {
+	let (_, page) = sender
+		.execute_with(|| XcmpQueue::take_outbound_messages(usize::MAX))
+		.pop()
+		.expect("a message is sent");
+	let mut data = &page[..];
+	assert_eq!(
+		XcmpMessageFormat::decode(&mut data),
+		Ok(XcmpMessageFormat::ConcatenatedVersionedXcm)
+	);
+	let message = VersionedXcm::<RuntimeCall>::decode(&mut data).unwrap();
+	receiver.execute_with(|| {
+		let mut hash = [0; 32];
+		XcmExecutor::<XcmConfig>::prepare_and_execute(
+			sibling(sender_id),
+			message.try_into().unwrap(),
+			&mut hash,
+			Weight::MAX,
+			Weight::zero(),
+		)
+	})
+}
+
+fn transfer(from: AccountId, to: AccountId, dest: u32, asset: Asset) -> sp_runtime::DispatchResult {
+	PolkadotXcm::transfer_assets(
+		RuntimeOrigin::signed(from),
+		Box::new(sibling(dest).into()),
+		Box::new(Location::new(0, [AccountId32 { network: None, id: to.into() }]).into()),
+		Box::new(asset.into()),
+		0,
+		Unlimited,
+	)
+}
+
+#[test]
+fn native_tokens_are_reserve_transferred_to_the_sibling() {
+	let mut university = new_chain(UNIVERSITY, COMPANY);
+	let mut company = new_chain(COMPANY, UNIVERSITY);
+
+	university.execute_with(|| {
+		assert_ok!(transfer(ALICE, BOB, COMPANY, (Here, 100 * UNIT).into()));
+		// The university keeps the tokens for the company, as their reserve
+		assert_eq!(Balances::balance(&ALICE), INITIAL_BALANCE - 100 * UNIT);
+		assert_eq!(Balances::balance(&sovereign_account(COMPANY)), 100 * UNIT);
+	});
+
+	assert!(matches!(deliver(&mut university, UNIVERSITY, &mut company), Outcome::Complete { .. }));
+	company.execute_with(|| {
+		let received = ForeignAssets::balance(sibling(UNIVERSITY), BOB);
+		assert!(received > 99 * UNIT && received < 100 * UNIT);
+		// The rest paid for the execution
+		assert_eq!(ForeignAssets::total_issuance(sibling(UNIVERSITY)), 100 * UNIT);
+	});
+}
+
+#[test]
+fn sibling_tokens_return_to_their_reserve() {
+	let mut university = new_chain(UNIVERSITY, COMPANY);
+	let mut company = new_chain(COMPANY, UNIVERSITY);
+	university.execute_with(|| {
+		assert_ok!(transfer(ALICE, BOB, COMPANY, (Here, 100 * UNIT).into()));
+	});
+	deliver(&mut university, UNIVERSITY, &mut company);
+
+	company.execute_with(|| {
+		let balance = ForeignAssets::balance(sibling(UNIVERSITY), BOB);
+		assert_ok!(transfer(BOB, ALICE, UNIVERSITY, (sibling(UNIVERSITY), 50 * UNIT).into()));
+		assert_eq!(ForeignAssets::balance(sibling(UNIVERSITY), BOB), balance - 50 * UNIT);
+		assert_eq!(ForeignAssets::total_issuance(sibling(UNIVERSITY)), 50 * UNIT);
+	});
+
+	assert!(matches!(deliver(&mut company, COMPANY, &mut university), Outcome::Complete { .. }));
+	university.execute_with(|| {
+		assert_eq!(Balances::balance(&sovereign_account(COMPANY)), 50 * UNIT);
+		let received = Balances::balance(&ALICE) - (INITIAL_BALANCE - 100 * UNIT);
+		assert!(received > 49 * UNIT && received < 50 * UNIT);
+	});
+}
+
+#[test]
+fn tokens_of_both_chains_are_transferred_in_the_same_way() {
+	let mut university = new_chain(UNIVERSITY, COMPANY);
+	let mut company = new_chain(COMPANY, UNIVERSITY);
+
+	company.execute_with(|| {
+		assert_ok!(transfer(ALICE, BOB, UNIVERSITY, (Here, 100 * UNIT).into()));
+		assert_eq!(Balances::balance(&sovereign_account(UNIVERSITY)), 100 * UNIT);
+	});
+	assert!(matches!(deliver(&mut company, COMPANY, &mut university), Outcome::Complete { .. }));
+
+	university.execute_with(|| {
+		assert!(ForeignAssets::balance(sibling(COMPANY), BOB) > 99 * UNIT);
+		assert_ok!(transfer(BOB, ALICE, COMPANY, (sibling(COMPANY), 50 * UNIT).into()));
+	});
+	assert!(matches!(deliver(&mut university, UNIVERSITY, &mut company), Outcome::Complete { .. }));
+	company.execute_with(|| {
+		assert_eq!(Balances::balance(&sovereign_account(UNIVERSITY)), 50 * UNIT);
+	});
+}
+
+#[test]
+fn relay_tokens_are_deposited_as_a_foreign_asset() {
+	new_chain(UNIVERSITY, COMPANY).execute_with(|| {
+		assert_ok!(ForeignAssets::force_create(
+			RuntimeOrigin::root(),
+			Location::parent(),
+			ALICE.into(),
+			true,
+			1
+		));
+		assert_ok!(AssetRate::create(
+			RuntimeOrigin::root(),
+			Box::new(Location::parent()),
+			FixedU128::from_u32(1)
+		));
+
+		let asset: Asset = (Parent, 100 * UNIT).into();
+		let message = Xcm::<RuntimeCall>(vec![
+			ReserveAssetDeposited(asset.clone().into()),
+			ClearOrigin,
+			BuyExecution { fees: asset, weight_limit: Unlimited },
+			DepositAsset {
+				assets: Wild(AllCounted(1)),
+				beneficiary: Location::new(0, [AccountId32 { network: None, id: BOB.into() }]),
+			},
+		]);
+		let mut hash = [0; 32];
+		let outcome = XcmExecutor::<XcmConfig>::prepare_and_execute(
+			Location::parent(),
+			message,
+			&mut hash,
+			Weight::MAX,
+			Weight::zero(),
+		);
+		assert!(matches!(outcome, Outcome::Complete { .. }));
+
+		// Kept apart from the native token
+		assert!(ForeignAssets::balance(Location::parent(), BOB) > 99 * UNIT);
+		assert_eq!(Balances::balance(&BOB), 0);
+	});
+}
+
+/// Fee paid on the company for a transfer of university tokens, at `rate` to its native token.
+fn fee_at_rate(rate: Option<FixedU128>) -> (Outcome, Balance) {
+	let mut university = new_chain(UNIVERSITY, COMPANY);
+	let mut company = new_chain(COMPANY, UNIVERSITY);
+	company.execute_with(|| {
+		let asset_kind = Box::new(sibling(UNIVERSITY));
+		assert_ok!(match rate {
+			Some(rate) => AssetRate::update(RuntimeOrigin::root(), asset_kind, rate),
+			None => AssetRate::remove(RuntimeOrigin::root(), asset_kind),
+		});
+	});
+	university.execute_with(|| {
+		assert_ok!(transfer(ALICE, BOB, COMPANY, (Here, 100 * UNIT).into()));
+	});
+	let outcome = deliver(&mut university, UNIVERSITY, &mut company);
+	let fee =
+		company.execute_with(|| 100 * UNIT - ForeignAssets::balance(sibling(UNIVERSITY), BOB));
+	(outcome, fee)
+}
+
+#[test]
+fn sibling_tokens_pay_for_execution_at_their_rate() {
+	let (_, at_par) = fee_at_rate(Some(FixedU128::from_u32(1)));
+	let (outcome, fee) = fee_at_rate(Some(FixedU128::from_u32(4)));
+	assert!(matches!(outcome, Outcome::Complete { .. }));
+	assert!(at_par > 0);
+	assert_eq!(fee, at_par / 4);
+}
+
+#[test]
+fn sibling_tokens_without_a_rate_cannot_pay_for_execution() {
+	let (outcome, fee) = fee_at_rate(None);
+	assert!(matches!(outcome, Outcome::Incomplete { error: XcmError::TooExpensive, .. }));
+	// Nothing was deposited
+	assert_eq!(fee, 100 * UNIT);
+}
+
+#[test]
+fn only_native_and_sibling_tokens_are_reserve_transferred() {
+	new_chain(UNIVERSITY, COMPANY).execute_with(|| {
+		assert_noop!(
+			transfer(ALICE, BOB, COMPANY, (Parent, 100 * UNIT).into()),
+			pallet_xcm::Error::<crate::Runtime>::Filtered
+		);
+		assert_noop!(
+			PolkadotXcm::limited_teleport_assets(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(sibling(COMPANY).into()),
+				Box::new(Location::new(0, [AccountId32 { network: None, id: BOB.into() }]).into()),
+				Box::new((Here, 100 * UNIT).into()),
+				0,
+				Unlimited,
+			),
+			pallet_xcm::Error::<crate::Runtime>::Filtered
+		);
+	});
+}
+
+#[test]
+fn graduations_are_dry_run_with_the_forwarded_message() {
+	use xcm_runtime_apis::dry_run::runtime_decl_for_dry_run_api::DryRunApiV2;
+
+	let mut university = new_chain(UNIVERSITY, COMPANY);
+	let mut company = new_chain(COMPANY, UNIVERSITY);
+	for chain in [&mut university, &mut company] {
+		chain.execute_with(|| {
+			assert_ok!(TemplatePallet::register_institution(
+				RuntimeOrigin::root(),
+				b"Polkadot University".to_vec(),
+				vec![ALICE]
+			));
+			assert_ok!(TemplatePallet::set_accreditation(
+				RuntimeOrigin::root(),
+				0,
+				pallet_parachain_template::Accreditation::Accredited
+			));
+		});
+	}
+
+	let forwarded = university.execute_with(|| {
+		// Mid-2025, for the date of birth to be in the past
+		pallet_timestamp::Now::<Runtime>::put(1_750_000_000_000);
+		assert_ok!(TemplatePallet::create_student(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			pallet_parachain_template::PersonalDataInput {
+				given_name: b"Ada".to_vec(),
+				middle_name: vec![],
+				family_name: b"Lovelace".to_vec(),
+				date_of_birth: pallet_parachain_template::Date { year: 2004, month: 6, day: 16 },
+				gender: pallet_parachain_template::Gender::Female,
+				national_id_hash: None,
+				email_hash: None,
+			},
+		));
+		let call = RuntimeCall::TemplatePallet(pallet_parachain_template::Call::graduate_student {
+			student_id: 0,
+			beneficiary: None,
+		});
+		let effects = Runtime::dry_run_call(
+			frame_system::RawOrigin::Signed(ALICE).into(),
+			call,
+			xcm::latest::VERSION,
+		)
+		.unwrap();
+		assert_ok!(effects.execution_result);
+
+		let [(destination, messages)] = &effects.forwarded_xcms[..] else {
+			panic!("one destination")
+		};
+		assert_eq!(*destination, sibling(COMPANY).into());
+		let [message] = &messages[..] else { panic!("one message") };
+		let instructions: Xcm<()> = message.clone().try_into().unwrap();
+		assert!(instructions.0.iter().any(|instruction| matches!(
+			instruction,
+			Transact { call, .. } if matches!(
+				RuntimeCall::decode(&mut &call.clone().into_encoded()[..]),
+				Ok(RuntimeCall::TemplatePallet(
+					pallet_parachain_template::Call::receive_student { source_id: 0, .. }
+				))
+			)
+		)));
+		message.clone()
+	});
+
+	// The forwarded message is dry-run against the destination in turn
+	company.execute_with(|| {
+		let forwarded = VersionedXcm::<RuntimeCall>::decode(&mut &forwarded.encode()[..]).unwrap();
+		let effects = Runtime::dry_run_xcm(sibling(UNIVERSITY).into(), forwarded).unwrap();
+		assert!(matches!(effects.execution_result, Outcome::Complete { .. }));
+	});
+}
+
+/// Apply the JSON patch of a genesis preset to `base`, as the genesis builder does.
+fn merge(base: &mut Value, patch: Value) {
+	match (base, patch) {
+		(Value::Object(base), Value::Object(patch)) =>
+			for (key, value) in patch {
+				merge(base.entry(key).or_insert(Value::Null), value);
+			},
+		(base, patch) => *base = patch,
+	}
+}
+
+/// A chain built from the council preset.
+fn council_chain() -> sp_io::TestExternalities {
+	let mut config = serde_json::to_value(RuntimeGenesisConfig::default()).unwrap();
+	let preset = genesis_config_presets::get_preset(&COUNCIL_RUNTIME_PRESET.into()).unwrap();
+	merge(&mut config, serde_json::from_slice(&preset).unwrap());
+
+	let mut ext = sp_io::TestExternalities::new_empty();
+	ext.execute_with(|| {
+		build_state::<RuntimeGenesisConfig>(serde_json::to_vec(&config).unwrap()).unwrap();
+		System::set_block_number(1);
+	});
+	ext
+}
+
+/// Propose `call` as Alice with `threshold` approvals, approve it with as many members and close
+/// it.
+fn pass_motion(call: RuntimeCall, threshold: u32) {
+	let council = [Sr25519Keyring::Alice, Sr25519Keyring::Bob, Sr25519Keyring::Charlie]
+		.map(|k| k.to_account_id());
+	let length = call.encoded_size() as u32;
+	let weight = call.get_dispatch_info().call_weight;
+	let hash = <Runtime as frame_system::Config>::Hashing::hash_of(&call);
+	let index = pallet_collective::ProposalCount::<Runtime, CouncilCollective>::get();
+
+	assert_ok!(Council::propose(
+		RuntimeOrigin::signed(council[0].clone()),
+		threshold,
+		Box::new(call),
+		length
+	));
+	if threshold > 1 {
+		for member in &council[..threshold as usize] {
+			assert_ok!(Council::vote(RuntimeOrigin::signed(member.clone()), hash, index, true));
+		}
+		assert_ok!(Council::close(
+			RuntimeOrigin::signed(council[0].clone()),
+			hash,
+			index,
+			weight,
+			length
+		));
+	}
+}
+
+fn last_motion_result() -> sp_runtime::DispatchResult {
+	System::events()
+		.into_iter()
+		.rev()
+		.find_map(|record| match record.event {
+			RuntimeEvent::Council(
+				pallet_collective::Event::Executed { result, .. } |
+				pallet_collective::Event::MemberExecuted { result, .. },
+			) => Some(result),
+			_ => None,
+		})
+		.expect("a motion is executed")
+}
+
+#[test]
+fn institutions_keep_their_own_scholarship_funds() {
+	new_chain(UNIVERSITY, COMPANY).execute_with(|| {
+		for name in [b"Polkadot University".to_vec(), b"Kusama Institute".to_vec()] {
+			assert_ok!(TemplatePallet::register_institution(
+				RuntimeOrigin::root(),
+				name,
+				vec![ALICE]
+			));
+		}
+		assert_ok!(TemplatePallet::fund_scholarships(RuntimeOrigin::signed(ALICE), 1, 100 * UNIT));
+
+		let fund = |institution_id| {
+			Balances::balance(&TemplatePallet::institution_account(institution_id))
+		};
+		assert_eq!(fund(0), 0);
+		assert_eq!(fund(1), 100 * UNIT);
+	});
+}
+
+#[test]
+fn council_preset_replaces_sudo() {
+	council_chain().execute_with(|| {
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		assert_noop!(
+			Sudo::sudo(
+				RuntimeOrigin::signed(Sr25519Keyring::Alice.to_account_id()),
+				Box::new(remark)
+			),
+			pallet_sudo::Error::<Runtime>::RequireSudo
+		);
+		assert_eq!(pallet_collective::Members::<Runtime, CouncilCollective>::get().len(), 3);
+		// No council member administers the first institution on their own
+		let institution = pallet_parachain_template::Institutions::<Runtime>::get(0).unwrap();
+		assert!(institution.admins.is_empty());
+	});
+}
+
+#[test]
+fn the_council_appoints_the_admins_of_an_institution() {
+	council_chain().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		assert_noop!(
+			TemplatePallet::set_institution_admins(
+				RuntimeOrigin::signed(alice.clone()),
+				0,
+				vec![alice.clone()]
+			),
+			DispatchError::BadOrigin
+		);
+
+		pass_motion(
+			RuntimeCall::TemplatePallet(pallet_parachain_template::Call::set_institution_admins {
+				institution_id: 0,
+				admins: vec![alice.clone()],
+			}),
+			2,
+		);
+		assert_ok!(last_motion_result());
+		let institution = pallet_parachain_template::Institutions::<Runtime>::get(0).unwrap();
+		assert_eq!(institution.admins.into_inner(), vec![alice.clone()]);
+
+		// Once appointed, an admin still cannot change the set on their own
+		assert_noop!(
+			TemplatePallet::set_institution_admins(RuntimeOrigin::signed(alice), 0, vec![]),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn two_thirds_of_the_council_administer_the_registry() {
+	council_chain().execute_with(|| {
+		let register =
+			RuntimeCall::TemplatePallet(pallet_parachain_template::Call::register_institution {
+				name: b"Kusama Institute".to_vec(),
+				admins: vec![BOB],
+			});
+		assert_noop!(
+			TemplatePallet::register_institution(
+				RuntimeOrigin::signed(Sr25519Keyring::Alice.to_account_id()),
+				b"Kusama Institute".to_vec(),
+				vec![BOB],
+			),
+			DispatchError::BadOrigin
+		);
+
+		// A single member is not enough
+		pass_motion(register.clone(), 1);
+		assert_eq!(last_motion_result(), Err(DispatchError::BadOrigin));
+		assert_eq!(pallet_parachain_template::InstitutionCount::<Runtime>::get(), 1);
+
+		pass_motion(register, 2);
+		assert_ok!(last_motion_result());
+		assert_eq!(pallet_parachain_template::InstitutionCount::<Runtime>::get(), 2);
+
+		// So is the collator selection
+		pass_motion(
+			RuntimeCall::CollatorSelection(pallet_collator_selection::Call::set_candidacy_bond {
+				bond: 42 * UNIT,
+			}),
+			2,
+		);
+		assert_ok!(last_motion_result());
+		assert_eq!(pallet_collator_selection::CandidacyBond::<Runtime>::get(), 42 * UNIT);
+	});
+}
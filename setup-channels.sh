@@ -3,7 +3,7 @@
 echo "⏳ Waiting for relay chain to be ready..."
 sleep 15
 
-echo "📡 Opening HRMP channel 1000 -> 2000..."
+echo "📡 Opening HRMP channels 1000 <-> 2000..."
 
 # Install if not present
 if ! command -v polkadot-js-api &> /dev/null; then
@@ -19,4 +19,41 @@ polkadot-js-api \
   --seed "//Alice" \
   tx.hrmp.forceOpenHrmpChannel 1000 2000 1 102400
 
-echo "✅ HRMP channel opened!"
+# Open channel: 2000 -> 1000
+polkadot-js-api \
+  --ws ws://127.0.0.1:9944 \
+  --sudo \
+  --seed "//Alice" \
+  tx.hrmp.forceOpenHrmpChannel 2000 1000 1 102400
+
+echo "✅ HRMP channels opened!"
+
+echo "🪙 Registering the token of each chain on the other one..."
+
+# Token of 2000 on 1000, and of 1000 on 2000, sufficient and at par
+polkadot-js-api \
+  --ws ws://127.0.0.1:9988 \
+  --sudo \
+  --seed "//Alice" \
+  tx.foreignAssets.forceCreate '{"parents":1,"interior":{"X1":[{"Parachain":2000}]}}' 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY true 1000000000
+
+polkadot-js-api \
+  --ws ws://127.0.0.1:9999 \
+  --sudo \
+  --seed "//Alice" \
+  tx.foreignAssets.forceCreate '{"parents":1,"interior":{"X1":[{"Parachain":1000}]}}' 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY true 1000000000
+
+# Both pay for XCM execution at par with the native token (a `FixedU128` of 1)
+polkadot-js-api \
+  --ws ws://127.0.0.1:9988 \
+  --sudo \
+  --seed "//Alice" \
+  tx.assetRate.create '{"parents":1,"interior":{"X1":[{"Parachain":2000}]}}' 1000000000000000000
+
+polkadot-js-api \
+  --ws ws://127.0.0.1:9999 \
+  --sudo \
+  --seed "//Alice" \
+  tx.assetRate.create '{"parents":1,"interior":{"X1":[{"Parachain":1000}]}}' 1000000000000000000
+
+echo "✅ Foreign assets registered!"